@@ -1,37 +1,95 @@
 use std::path::Path;
 
 use crate::models::{
+    hooks::{HookStage, Hooks},
     project_config::{ProjectConfig, ProjectMetaData},
     project_index::ProjectIndex,
 };
 
+/// The path or the name is already registered, adding the project again is a no-op
+#[derive(Debug)]
+pub struct AlreadyRegistered;
+
+impl std::fmt::Display for AlreadyRegistered {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Project already exist")
+    }
+}
+
+impl std::error::Error for AlreadyRegistered {}
+
 pub fn execute(path: &Path, project_name: &Option<String>) {
-    let project_meta_data = match ProjectMetaData::new(path, project_name.clone()) {
-        Ok(meta) => meta,
-        Err(e) => {
-            eprintln!("Error creating project metadata: {:?}", e);
-            std::process::exit(1)
+    let project_config = register(path, project_name).unwrap_or_else(|e| {
+        if e.is::<AlreadyRegistered>() {
+            println!("{}", e);
+            std::process::exit(0);
         }
-    };
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1)
+    });
+
+    run_hooks(&project_config);
+}
+
+/// Saves the config of the project and adds it to the index, the hooks are not run.
+pub fn register(
+    path: &Path,
+    project_name: &Option<String>,
+) -> Result<ProjectConfig, anyhow::Error> {
+    let project_meta_data = ProjectMetaData::new(path, project_name.clone())
+        .map_err(|e| e.context("Error creating project metadata"))?;
 
     let project_config = ProjectConfig::new(project_meta_data);
 
-    let mut project_index = ProjectIndex::load_or_new();
+    let mut project_index = ProjectIndex::load_for_update()?;
 
     if project_index.project_exists(
         &project_config.meta_data.name,
         &project_config.meta_data.path,
     ) {
-        println!("Project already exist");
-        std::process::exit(0);
+        return Err(AlreadyRegistered.into());
     }
 
-    if let Err(e) = project_config.save() {
-        eprintln!("Error saving project config: {:?}", e);
-        std::process::exit(1)
-    };
-    if let Err(e) = project_index.add_project_and_save(project_config.meta_data) {
-        eprintln!("Error adding project to index: {:?}", e);
-        std::process::exit(1)
+    // Checked before the config is written so a refused project leaves nothing behind
+    project_index.check_new_project(&project_config.meta_data)?;
+
+    project_config
+        .save()
+        .map_err(|e| e.context("Error saving project config"))?;
+    project_index
+        .add_project_and_save(project_config.meta_data.clone())
+        .map_err(|e| e.context("Error adding project to index"))?;
+
+    Ok(project_config)
+}
+
+/// Runs the `on_add` hooks of a registered project, exits when they abort.
+pub fn run_hooks(project_config: &ProjectConfig) {
+    let global_hooks = crate::config::get_config()
+        .unwrap()
+        .read()
+        .unwrap()
+        .settings
+        .hooks
+        .clone();
+    let hooks = Hooks::merge(&global_hooks, &project_config.hooks);
+
+    // The hooks run once the project is registered, a failure doesn't unregister it
+    if let Err(e) = hooks.run(
+        HookStage::Add,
+        Path::new(&project_config.meta_data.path),
+        &project_config.environment(),
+        false,
+    ) {
+        exit_registered_anyway(e, &project_config.meta_data.name);
     }
 }
+
+/// Reports a hook failure of a project which stays registered, then exits.
+pub fn exit_registered_anyway(e: anyhow::Error, project_name: &str) -> ! {
+    eprintln!(
+        "Error: {:?}\nThe project {} is registered anyway, run `delete {}` to remove it",
+        e, project_name, project_name
+    );
+    std::process::exit(1)
+}
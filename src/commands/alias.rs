@@ -0,0 +1,127 @@
+use clap::Subcommand;
+use clap_complete::ArgValueCandidates;
+
+use super::{
+    completions::project_name_candidates,
+    list_field::{self, ListField},
+};
+
+use crate::models::{project_config::ProjectMetaData, project_index::ProjectIndex};
+
+#[derive(Subcommand, Debug)]
+pub enum AliasAction {
+    /// Add aliases opening a project
+    Add {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        #[arg(required = true)]
+        aliases: Vec<String>,
+    },
+    /// Remove aliases from a project
+    Rm {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        #[arg(required = true)]
+        aliases: Vec<String>,
+    },
+    /// List the aliases of a project, or every alias with its project
+    Ls {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: Option<String>,
+    },
+}
+
+/// The `aliases` of a project, an alias must open a single project so collisions are checked when saving
+struct Aliases;
+
+impl ListField for Aliases {
+    const NAME: &'static str = "alias";
+    const PLURAL: &'static str = "aliases";
+    const ADDED: &'static str = "aliased";
+    const REMOVED: &'static str = "unaliased";
+
+    fn values(project: &ProjectMetaData) -> &Vec<String> {
+        &project.aliases
+    }
+
+    fn values_mut(project: &mut ProjectMetaData) -> &mut Vec<String> {
+        &mut project.aliases
+    }
+
+    fn missing(project_name: &str, alias: &str) -> anyhow::Error {
+        anyhow::anyhow!("{} is not an alias of {}", alias, project_name)
+    }
+
+    /// `-` and `last` reopen the last project
+    fn check(alias: &str) -> Result<(), anyhow::Error> {
+        if alias == "-" || alias == "last" {
+            return Err(anyhow::anyhow!(
+                "`{}` is reserved to reopen the last project",
+                alias
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn execute(action: &AliasAction) {
+    let result = match action {
+        AliasAction::Add {
+            project_name,
+            aliases,
+        } => list_field::add::<Aliases>(project_name, aliases),
+        AliasAction::Rm {
+            project_name,
+            aliases,
+        } => list_field::remove::<Aliases>(project_name, aliases),
+        AliasAction::Ls {
+            project_name: Some(project_name),
+        } => list_field::list_project::<Aliases>(project_name),
+        AliasAction::Ls { project_name: None } => {
+            list_all();
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Prints every alias with its project
+fn list_all() {
+    let project_index = ProjectIndex::load_or_new();
+
+    let mut aliases: Vec<(&str, &str)> = project_index
+        .projects
+        .iter()
+        .flat_map(|project| {
+            project
+                .aliases
+                .iter()
+                .map(|alias| (alias.as_str(), project.name.as_str()))
+        })
+        .collect();
+    aliases.sort();
+
+    for (alias, project_name) in aliases {
+        println!("{} -> {}", alias, project_name);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_alias() {
+        assert!(list_field::validate::<Aliases>("api").is_ok());
+        assert!(list_field::validate::<Aliases>("web-2").is_ok());
+        assert!(list_field::validate::<Aliases>("").is_err());
+        assert!(list_field::validate::<Aliases>("my api").is_err());
+        assert!(list_field::validate::<Aliases>("tag:rust").is_err());
+        assert!(list_field::validate::<Aliases>("-").is_err());
+        assert!(list_field::validate::<Aliases>("last").is_err());
+    }
+}
@@ -1,91 +1,338 @@
-use std::{io::stdin, process::Command};
-
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    io,
+    path::Path,
+    process::{Child, Command},
+};
+
+use chrono::{DateTime, Utc};
 use cli_project_manager::lib;
 
-use crate::models::{project_config::ProjectMetaData, project_index::ProjectIndex};
+use super::{
+    picker,
+    query::{score_project, MatchWeights, Query},
+};
+use crate::models::{
+    hooks::{HookStage, Hooks},
+    project_config::{ProjectConfig, ProjectMetaData},
+    project_index::ProjectIndex,
+    settings::{EditorSettings, ShellMode, ShellSettings},
+};
+
+#[derive(Debug, Default)]
+pub struct OpenOptions {
+    /// Skip launching the editor
+    pub no_editor: bool,
+    /// Print a `cd` command instead of spawning a subshell
+    pub cd: bool,
+    /// Only print the project path, used by the shell integration
+    pub resolve: bool,
+    /// Print the environment of the project as `export` commands instead of opening it
+    pub print_env: bool,
+}
 
-pub fn execute(project_name: String) {
-    let project_index = ProjectIndex::load_or_new();
+pub fn execute(project_name: String, options: &OpenOptions) {
+    let mut project_index = ProjectIndex::load_or_new();
 
     if project_index.projects.is_empty() {
-        println!("List of projects is empty!");
+        eprintln!("List of projects is empty!");
         return;
     }
 
-    let project_meta_data = find_or_fuzzing_match_project(&project_index, &project_name);
+    let project_meta_data = find_or_fuzzing_match_project(&project_index, &project_name).clone();
+    let project_config = load_project_config(&project_meta_data);
+    let environment = project_config.environment();
+
+    let shell_settings = project_config.shell.clone().unwrap_or_else(|| {
+        crate::config::get_config()
+            .unwrap()
+            .read()
+            .unwrap()
+            .settings
+            .shell
+            .clone()
+    });
+
+    if options.print_env {
+        // Same precedence as the spawned shell, the project environment wins over `shell.env`
+        let mut shell_environment = shell_settings.env.clone();
+        shell_environment.extend(environment);
+
+        for (key, value) in &shell_environment {
+            // The project env is checked when loaded, `shell.env` keys come from the global config
+            if !lib::environment::is_valid_name(key) {
+                eprintln!("Warning: skipping the invalid variable name `{}`", key);
+                continue;
+            }
+            println!("export {}={}", key, lib::utils::shell_word(value));
+        }
+        return;
+    }
+
+    let shell_mode = if options.cd {
+        ShellMode::Cd
+    } else {
+        shell_settings.mode
+    };
+
+    // In cd and resolve modes our stdout is read by the calling shell
+    let stdout_captured = options.resolve || shell_mode == ShellMode::Cd;
+
+    let hooks = Hooks::merge(
+        &crate::config::get_config()
+            .unwrap()
+            .read()
+            .unwrap()
+            .settings
+            .hooks,
+        &project_config.hooks,
+    );
+    let project_path = Path::new(&project_meta_data.path);
+
+    if let Err(e) = hooks.run(HookStage::Open, project_path, &environment, stdout_captured) {
+        eprintln!("Opening aborted: {:?}", e);
+        std::process::exit(1);
+    }
+
+    // Usage stats are best effort, they must not prevent opening the project
+    if let Err(e) = project_index.record_open_and_save(&project_meta_data.name) {
+        eprintln!("Warning: failed to record the opening: {:?}", e);
+    }
+
+    let mut editor = None;
+    if !options.no_editor {
+        let editor_settings = project_config.editor.clone().unwrap_or_else(|| {
+            crate::config::get_config()
+                .unwrap()
+                .read()
+                .unwrap()
+                .settings
+                .editor
+                .clone()
+        });
+
+        if editor_settings.enabled {
+            editor = launch_editor(
+                &editor_settings,
+                &project_meta_data,
+                &environment,
+                stdout_captured,
+            );
+        }
+    }
 
-    // Lunch vscode with code .
-    spawn_editor(project_meta_data);
+    if options.resolve {
+        println!("{}", project_meta_data.path);
+    } else {
+        match shell_mode {
+            ShellMode::Cd => {
+                println!("cd -- {}", lib::utils::shell_quote(&project_meta_data.path));
+            }
+            ShellMode::Spawn => {
+                spawn_shell(&shell_settings, &project_meta_data, &environment);
+
+                // There is no session to wait for in the other modes, so on_close only runs here
+                if let Err(e) = hooks.run(HookStage::Close, project_path, &environment, false) {
+                    eprintln!("Error: {:?}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    // Reap the editor launcher if it already exited
+    if let Some(editor) = editor.as_mut() {
+        let _ = editor.try_wait();
+    }
+}
 
-    let mut shell = Command::new("zsh")
-        .env("PROJECT_NAME", &project_meta_data.name)
+fn spawn_shell(
+    shell_settings: &ShellSettings,
+    project_meta_data: &ProjectMetaData,
+    environment: &BTreeMap<String, String>,
+) {
+    let shell_program = shell_settings.program();
+
+    let mut shell = Command::new(&shell_program)
+        .args(&shell_settings.args)
+        .envs(&shell_settings.env)
+        .envs(environment)
         .current_dir(&project_meta_data.path)
         .spawn()
-        .expect("Failed to spawn shell");
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to spawn shell {}: {}", shell_program, e);
+            std::process::exit(1);
+        });
 
     shell.wait().expect("Failed to wait shell processus");
 }
 
+fn load_project_config(project_meta_data: &ProjectMetaData) -> ProjectConfig {
+    ProjectConfig::load(&project_meta_data.name).unwrap_or_else(|e| {
+        eprintln!("Warning: {:?}, using the default settings", e);
+        ProjectConfig::new(project_meta_data.clone())
+    })
+}
+
 fn find_or_fuzzing_match_project<'a>(
     project_index: &'a ProjectIndex,
     project_name: &'a str,
 ) -> &'a ProjectMetaData {
-    // 1. Check for an exact match first
+    // 1. Check for an exact match first, on the names then the aliases. `-` and `last` reopen the previous project
     if let Ok(project_meta_data) = project_index.find_project_by_name(project_name) {
         return project_meta_data;
     }
 
-    // 2. Perform fuzzy matching if no exact match is found
+    if let Some(project_meta_data) = project_index.find_project_by_alias(project_name) {
+        return project_meta_data;
+    }
 
-    let project_names: Vec<&str> = project_index
+    if project_name == "-" || project_name == "last" {
+        return project_index.last_opened_project().unwrap_or_else(|| {
+            eprintln!("No project has been opened yet");
+            std::process::exit(1);
+        });
+    }
+
+    // `tag:` and `path:` filters narrow the candidates
+    let query = Query::parse(project_name);
+    let projects: Vec<&ProjectMetaData> = project_index
         .projects
         .iter()
-        .map(|p| p.name.as_str())
+        .filter(|project| query.accepts(project))
         .collect();
 
-    let fuzzed_matches = lib::fuzzing_matching::matching(project_names, project_name);
+    if projects.is_empty() {
+        eprintln!("No project matches {}", project_name);
+        std::process::exit(1);
+    }
+
+    // Without a pattern, pick among the remaining projects, most recently opened first
+    if query.pattern.is_empty() {
+        if query.has_filters() && projects.len() == 1 {
+            return projects[0];
+        }
+
+        let mut projects = projects;
+        projects.sort_by_key(|project| {
+            Reverse(
+                project
+                    .last_opened_utc
+                    .as_deref()
+                    .and_then(lib::utils::parse_utc_date),
+            )
+        });
+        return pick_project(&projects);
+    }
+
+    // 2. Perform fuzzy matching if no exact match is found
+
+    let (score_gap, min_score, frecency_ratio, weights) = {
+        let config = crate::config::get_config().unwrap().read().unwrap();
+        let fuzzy = &config.settings.fuzzy;
+        (
+            fuzzy.score_gap,
+            fuzzy.min_score,
+            fuzzy.frecency_ratio,
+            MatchWeights {
+                path: fuzzy.path_weight,
+                tag: fuzzy.tag_weight,
+            },
+        )
+    };
+
+    let mut fuzzed_matches: HashMap<u32, Vec<&ProjectMetaData>> = HashMap::new();
+    for project in projects {
+        fuzzed_matches
+            .entry(score_project(project, &query.pattern, weights))
+            .or_default()
+            .push(project);
+    }
     let mut sorted_matches = lib::utils::sort_hashmap_by_keys(&fuzzed_matches);
+    sorted_matches.retain(|(score, _)| *score >= min_score);
+
+    if sorted_matches.is_empty() {
+        eprintln!("No project matches {}", project_name);
+        std::process::exit(1);
+    }
 
-    // The goal is to create an array that contains elements with a score difference of 20 or less.
+    // The goal is to create an array that contains elements with a score difference of `score_gap` or less.
     // This ensures that elements in the array are close in score, preventing the user from having
     // to choose between unrelated or vastly different options.
 
-    let filtered_matches = filter_by_score_gap(&mut sorted_matches, 20);
+    let filtered_matches = filter_by_score_gap(&mut sorted_matches, score_gap);
 
-    if filtered_matches.len() == 1 {
-        let matched_name = &filtered_matches[0].1[0];
-        return project_index
-            .find_project_by_name(matched_name)
-            .unwrap_or_else(|e| {
-                eprintln!(
-                    "Error finding project by name after fuzzing matching: {:?}",
-                    e
-                );
-                std::process::exit(1);
-            });
+    // Every project of the close matches, best score first
+    let mut candidates: Vec<&ProjectMetaData> = filtered_matches
+        .iter()
+        .flat_map(|(_, projects)| projects.iter().copied())
+        .collect();
+
+    if candidates.len() == 1 {
+        return candidates[0];
+    }
+
+    // 3. Prefer the project we obviously meant because we open it all the time
+    let now = Utc::now();
+    if let Some(project_meta_data) = break_tie_by_frecency(&candidates, frecency_ratio, now) {
+        return project_meta_data;
+    }
+
+    // 4. Handle mutliple fuzzy matches or low confidence matches by prompting the user, most used first
+    candidates.sort_by(|a, b| b.frecency(now).total_cmp(&a.frecency(now)));
+    pick_project(&candidates)
+}
+
+fn pick_project<'a>(projects: &[&'a ProjectMetaData]) -> &'a ProjectMetaData {
+    match picker::pick(projects) {
+        Ok(Some(project_meta_data)) => project_meta_data,
+        Ok(None) => {
+            eprintln!("No project selected");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Error selecting a project: {:?}", e);
+            std::process::exit(1);
+        }
     }
+}
 
-    // 4. Handle mutliple fuzzy matches or low confidence matches by prompting the user
-    let project_name = prompt_user_for_project_selection(&filtered_matches);
+/// Returns the candidate whose frecency is at least `ratio` times the one of every other candidate
+fn break_tie_by_frecency<'a>(
+    candidates: &[&'a ProjectMetaData],
+    ratio: f64,
+    now: DateTime<Utc>,
+) -> Option<&'a ProjectMetaData> {
+    if ratio <= 0.0 {
+        return None;
+    }
 
-    let project_meta_data = project_index
-        .find_project_by_name(&project_name)
-        .expect("Failed to find project");
+    let mut scored: Vec<(f64, &ProjectMetaData)> = candidates
+        .iter()
+        .map(|project| (project.frecency(now), *project))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
 
-    project_meta_data
+    match scored.as_slice() {
+        [(best, project), (second, _), ..] if *best > 0.0 && *best >= second * ratio => {
+            Some(project)
+        }
+        _ => None,
+    }
 }
 
-fn filter_by_score_gap<'a>(
-    sorted_matches: &mut [(u32, &'a Vec<String>)],
+fn filter_by_score_gap<'a, T>(
+    sorted_matches: &mut [(u32, &'a Vec<T>)],
     gap: u32,
-) -> Vec<(u32, &'a Vec<String>)> {
+) -> Vec<(u32, &'a Vec<T>)> {
     let mut filtered_matches = vec![sorted_matches[0]];
 
-    if sorted_matches[0].0 - sorted_matches[1].0 > gap {
+    if sorted_matches.len() == 1 || sorted_matches[0].0 - sorted_matches[1].0 > gap {
         return filtered_matches;
     }
 
-    let tmp: Vec<(u32, &Vec<String>)> = sorted_matches
+    let tmp: Vec<(u32, &Vec<T>)> = sorted_matches
         .windows(2)
         .filter(|window| window[0].0 - window[1].0 <= gap)
         .map(|win| win[1])
@@ -95,38 +342,39 @@ fn filter_by_score_gap<'a>(
     filtered_matches
 }
 
-fn prompt_user_for_project_selection(sorted_matches: &[(u32, &Vec<String>)]) -> String {
-    println!("Multiple projects matched. Please select one:");
-
-    // Display the list of projects to the user
-    for (i, (_, project_names)) in sorted_matches.iter().enumerate() {
-        println!("{}: {}", i, project_names[0]);
+/// Launches the editor in the project directory.
+/// Returns the editor process when it runs in the background, `None` when it was waited for or failed to start.
+fn launch_editor(
+    editor_settings: &EditorSettings,
+    project_meta_data: &ProjectMetaData,
+    environment: &BTreeMap<String, String>,
+    stdout_captured: bool,
+) -> Option<Child> {
+    let mut command = Command::new(&editor_settings.command);
+    command
+        .args(editor_settings.expand_args(&project_meta_data.name, &project_meta_data.path))
+        .envs(environment)
+        .current_dir(&project_meta_data.path);
+
+    // Keep the editor output away from the calling shell
+    if stdout_captured {
+        command.stdout(io::stderr());
     }
 
-    // Read user input and parse the selected index
-    let mut index_input = String::new();
-    let stdin = stdin();
-    stdin
-        .read_line(&mut index_input)
-        .expect("Failed to read input");
-    let index: usize = index_input
-        .trim()
-        .parse()
-        .expect("Invalid input. Please enter a number.");
-
-    // Get the selected project name and return its metadata
-    let selected_project_name = &sorted_matches[index].1[0];
-
-    String::from(selected_project_name)
-}
+    if editor_settings.wait {
+        if let Err(e) = command.status() {
+            eprintln!("Failed to run {}: {}", editor_settings.command, e);
+        }
+        return None;
+    }
 
-fn spawn_editor(project_meta_data: &ProjectMetaData) {
-    let _ = Command::new("code")
-        .env("PROJECT_NAME", &project_meta_data.name)
-        .arg(".")
-        .current_dir(&project_meta_data.path)
-        .spawn()
-        .expect("Failed to spawn code");
+    match command.spawn() {
+        Ok(child) => Some(child),
+        Err(e) => {
+            eprintln!("Failed to spawn {}: {}", editor_settings.command, e);
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -166,4 +414,39 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_filter_by_score_gap_single_match() {
+        let a = vec!["a".to_string()];
+        let mut single_match: Vec<(u32, &Vec<String>)> = vec![(40, &a)];
+
+        assert_eq!(filter_by_score_gap(&mut single_match, 20), vec![(40, &a)]);
+    }
+
+    #[test]
+    fn test_break_tie_by_frecency() {
+        let now = Utc::now();
+        let project = |name: &str, open_count: u32| {
+            let mut project =
+                ProjectMetaData::new(&tempfile::tempdir().unwrap().keep(), Some(name.to_string()))
+                    .unwrap();
+            project.open_count = open_count;
+            project.last_opened_utc = Some(now.to_string());
+            project
+        };
+        let daily = project("backend-service", 30);
+        let rare = project("backend-legacy", 2);
+        let other = project("backend-tools", 20);
+        let never = project("backend-new", 0);
+
+        let winner = break_tie_by_frecency(&[&rare, &daily], 2.0, now);
+        assert_eq!(winner.unwrap().name, "backend-service");
+
+        assert!(break_tie_by_frecency(&[&daily, &other], 2.0, now).is_none());
+        assert!(break_tie_by_frecency(&[&rare, &daily], 0.0, now).is_none());
+
+        let mut never_opened = never.clone();
+        never_opened.name = "backend-other".to_string();
+        assert!(break_tie_by_frecency(&[&never, &never_opened], 2.0, now).is_none());
+    }
 }
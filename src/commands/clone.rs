@@ -0,0 +1,91 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Context;
+use cli_project_manager::lib::git::RepositoryUrl;
+
+use crate::models::project_index::ProjectIndex;
+
+/// Clones a repository into the workspace root, or `dest`, then registers it like `add` does.
+pub fn execute(url: &str, project_name: &Option<String>, dest: &Option<PathBuf>) {
+    let (destination, project_name) = match prepare(url, project_name, dest.as_deref()) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let status = Command::new("git")
+        .arg("clone")
+        .arg(url)
+        .arg(&destination)
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!("git clone exited with {}", status);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to run git: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    crate::commands::add::execute(&destination, &Some(project_name));
+}
+
+/// Checks everything that would prevent registering the clone before cloning,
+/// returns the destination directory and the project name.
+fn prepare(
+    url: &str,
+    project_name: &Option<String>,
+    dest: Option<&Path>,
+) -> Result<(PathBuf, String), anyhow::Error> {
+    let destination = match dest {
+        Some(dest) => dest.to_path_buf(),
+        None => {
+            let repository_url = RepositoryUrl::parse(url)
+                .with_context(|| format!("Can't find the repository name in {}", url))?;
+            let root = crate::config::get_config()
+                .unwrap()
+                .read()
+                .unwrap()
+                .settings
+                .clone
+                .root_path();
+            repository_url.workspace_path(&root)
+        }
+    };
+
+    if destination.exists() {
+        return Err(anyhow::anyhow!(
+            "{} already exists, use --dest to clone elsewhere",
+            destination.display()
+        ));
+    }
+
+    let project_name = match project_name {
+        Some(project_name) => project_name.clone(),
+        None => destination
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .with_context(|| format!("Invalid destination {}", destination.display()))?,
+    };
+
+    ProjectIndex::load_or_new()
+        .ensure_name_available(&project_name)
+        .map_err(|e| anyhow::anyhow!("{}, use --name to choose another name", e))?;
+
+    if let Some(parent) = destination.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    Ok((destination, project_name))
+}
@@ -0,0 +1,47 @@
+use std::io;
+
+use clap::CommandFactory;
+use clap_complete::{env::Shells, CompletionCandidate, Shell};
+
+use crate::models::project_index::ProjectIndex;
+
+/// Environment variable used by the generated scripts to ask the binary for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Prints the completion script of `shell`.
+/// The script calls back into the binary so project names are always up to date.
+pub fn execute(shell: Shell) {
+    let bin = env!("CARGO_BIN_NAME");
+    let command = crate::Cli::command();
+
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .expect("Every clap_complete shell has a completer");
+
+    if let Err(e) = completer.write_registration(
+        COMPLETE_VAR,
+        command.get_name(),
+        bin,
+        bin,
+        &mut io::stdout(),
+    ) {
+        eprintln!("Failed to write completion script: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Registered project names, with their path as help.
+/// The global config isn't initialized while completing, the index is read directly and errors are ignored.
+pub fn project_name_candidates() -> Vec<CompletionCandidate> {
+    let index_file_path = crate::config::project_index_path(&crate::config::base_dir());
+
+    ProjectIndex::load_from_path(&index_file_path)
+        .unwrap_or_else(|_| ProjectIndex::new())
+        .projects
+        .iter()
+        .map(|project| {
+            CompletionCandidate::new(&project.name).help(Some(project.path.clone().into()))
+        })
+        .collect()
+}
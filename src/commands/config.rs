@@ -0,0 +1,179 @@
+use clap::Subcommand;
+use clap_complete::ArgValueCandidates;
+
+use super::completions::project_name_candidates;
+
+use crate::models::{project_config::ProjectConfig, project_index::ProjectIndex};
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Print the value of a key, e.g. `editor.command`
+    Get {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        key: String,
+    },
+    /// Set the value of a key, the value is parsed as TOML and falls back to a string
+    Set {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        key: String,
+        value: String,
+    },
+}
+
+pub fn execute(action: &ConfigAction) {
+    let result = match action {
+        ConfigAction::Get { project_name, key } => get(project_name, key),
+        ConfigAction::Set {
+            project_name,
+            key,
+            value,
+        } => set(project_name, key, value),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+fn get(project_name: &str, key: &str) -> Result<(), anyhow::Error> {
+    let config_value = load_config_value(project_name)?;
+
+    let value = key
+        .split('.')
+        .try_fold(&config_value, |value, segment| value.get(segment))
+        .ok_or_else(|| anyhow::anyhow!("Key {} not found", key))?;
+
+    match value {
+        toml::Value::String(string) => println!("{}", string),
+        toml::Value::Table(_) => print!("{}", toml::to_string_pretty(value)?),
+        other => println!("{}", other),
+    }
+
+    Ok(())
+}
+
+fn set(project_name: &str, key: &str, raw_value: &str) -> Result<(), anyhow::Error> {
+    if key == "meta_data" || key.starts_with("meta_data.") {
+        return Err(anyhow::anyhow!(
+            "meta_data is managed by the project index, use the rename, move, tag and alias commands"
+        ));
+    }
+
+    let config_value = load_config_value(project_name)?;
+    let project_config = match with_value(&config_value, key, parse_value(raw_value)) {
+        Ok(project_config) => project_config,
+        // `8080` or `true` may be meant as a string, like the values of the `env` table
+        Err(e) => match parse_value(raw_value) {
+            toml::Value::String(_) => return Err(e),
+            _ => with_value(
+                &config_value,
+                key,
+                toml::Value::String(raw_value.to_string()),
+            )
+            .map_err(|_| e)?,
+        },
+    };
+    project_config.save()?;
+
+    Ok(())
+}
+
+/// Sets `key` in a copy of the config and validates the result against the project config schema
+fn with_value(
+    config_value: &toml::Value,
+    key: &str,
+    value: toml::Value,
+) -> Result<ProjectConfig, anyhow::Error> {
+    let mut config_value = config_value.clone();
+    set_value(&mut config_value, key, value)?;
+
+    ProjectConfig::from_toml_str(&toml::to_string(&config_value)?)
+}
+
+fn load_config_value(project_name: &str) -> Result<toml::Value, anyhow::Error> {
+    ProjectIndex::load_or_new().find_project_by_name(project_name)?;
+
+    let project_config = ProjectConfig::load(project_name)?;
+    Ok(toml::Value::try_from(project_config)?)
+}
+
+/// Parses `raw_value` as a TOML value (`true`, `42`, `["a", "b"]`, ...), anything else is a string.
+fn parse_value(raw_value: &str) -> toml::Value {
+    toml::from_str::<toml::value::Table>(&format!("value = {}", raw_value))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw_value.to_string()))
+}
+
+/// Sets a dotted `key`, creating the missing intermediate tables.
+fn set_value(root: &mut toml::Value, key: &str, value: toml::Value) -> Result<(), anyhow::Error> {
+    let segments: Vec<&str> = key.split('.').collect();
+    let (last, parents) = segments
+        .split_last()
+        .ok_or_else(|| anyhow::anyhow!("Empty key"))?;
+
+    let mut current = root;
+    for segment in parents {
+        current = current
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("{} is not a table", key))?
+            .entry(segment.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::value::Table::new()));
+    }
+
+    current
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("{} is not a table", key))?
+        .insert(last.to_string(), value);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        let samples = vec![
+            ("true", toml::Value::Boolean(true)),
+            ("42", toml::Value::Integer(42)),
+            ("\"quoted\"", toml::Value::String("quoted".to_string())),
+            ("nvim", toml::Value::String("nvim".to_string())),
+            (
+                "code --wait",
+                toml::Value::String("code --wait".to_string()),
+            ),
+            (
+                "[\"--login\"]",
+                toml::Value::Array(vec![toml::Value::String("--login".to_string())]),
+            ),
+        ];
+
+        for (input, expected) in samples {
+            assert_eq!(parse_value(input), expected, "Failed on {}", input);
+        }
+    }
+
+    #[test]
+    fn test_set_value() {
+        let mut root: toml::Value = toml::from_str("[editor]\ncommand = \"code\"").unwrap();
+
+        set_value(&mut root, "editor.wait", toml::Value::Boolean(true)).unwrap();
+        set_value(
+            &mut root,
+            "env.RUST_LOG",
+            toml::Value::String("debug".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(root["editor"]["command"].as_str(), Some("code"));
+        assert_eq!(root["editor"]["wait"].as_bool(), Some(true));
+        assert_eq!(root["env"]["RUST_LOG"].as_str(), Some("debug"));
+
+        assert!(set_value(&mut root, "editor.command.name", toml::Value::Integer(1)).is_err());
+    }
+}
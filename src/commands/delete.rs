@@ -1,7 +1,10 @@
 use crate::models::project_index::ProjectIndex;
 
 pub fn execute(project_name: &str) {
-    let mut project_index = ProjectIndex::load_or_new();
+    let mut project_index = ProjectIndex::load_for_update().unwrap_or_else(|e| {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    });
 
     // TODO: Improve this
     match project_index.remove_project_by_name(project_name) {
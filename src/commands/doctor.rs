@@ -0,0 +1,365 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use cli_project_manager::lib::walk::{walk_dirs, WalkAction};
+
+use crate::models::{
+    project_config::{project_config_path, ProjectConfig},
+    project_index::ProjectIndex,
+};
+
+#[derive(Debug, PartialEq)]
+enum Problem {
+    MissingPath { name: String, path: String },
+    OrphanedConfig { name: String, file: PathBuf },
+    MissingConfig { name: String },
+    DuplicatePath { path: String, names: Vec<String> },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MissingPath { name, path } => {
+                write!(f, "{}: path {} doesn't exist", name, path)
+            }
+            Problem::OrphanedConfig { file, .. } => {
+                write!(f, "{}: config file without project", file.display())
+            }
+            Problem::MissingConfig { name } => write!(f, "{}: config file missing", name),
+            Problem::DuplicatePath { path, names } => {
+                write!(f, "{}: registered by {}", path, names.join(", "))
+            }
+        }
+    }
+}
+
+pub fn execute(fix: bool) {
+    let mut project_index = ProjectIndex::load_for_update().unwrap_or_else(|e| {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    });
+    let projects_dir = {
+        let config = crate::config::get_config().unwrap().read().unwrap();
+        config.base_dir.join("projects")
+    };
+
+    let problems = diagnose(&project_index, &projects_dir);
+
+    if problems.is_empty() {
+        println!("No problem found");
+        return;
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    if !fix {
+        println!(
+            "{} problem(s) found, run `doctor --fix` to repair them",
+            problems.len()
+        );
+        std::process::exit(1);
+    }
+
+    let mut unresolved = 0;
+    for problem in &problems {
+        match repair(problem, &mut project_index) {
+            Ok(action) => println!("Fixed: {}", action),
+            Err(e) => {
+                unresolved += 1;
+                eprintln!("Not fixed: {} ({:?})", problem, e);
+            }
+        }
+    }
+
+    if unresolved > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Cross-checks the index against the filesystem and the project config files.
+fn diagnose(project_index: &ProjectIndex, projects_dir: &Path) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut names_by_path: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+    for project in &project_index.projects {
+        names_by_path
+            .entry(&project.path)
+            .or_default()
+            .push(project.name.clone());
+    }
+
+    for (path, names) in names_by_path {
+        if names.len() > 1 {
+            problems.push(Problem::DuplicatePath {
+                path: path.to_string(),
+                names,
+            });
+        }
+    }
+
+    for project in &project_index.projects {
+        if !Path::new(&project.path).is_dir() {
+            problems.push(Problem::MissingPath {
+                name: project.name.clone(),
+                path: project.path.clone(),
+            });
+        }
+
+        if !projects_dir.join(format!("{}.toml", project.name)).exists() {
+            problems.push(Problem::MissingConfig {
+                name: project.name.clone(),
+            });
+        }
+    }
+
+    let mut config_files: Vec<PathBuf> = fs::read_dir(projects_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .collect()
+        })
+        .unwrap_or_default();
+    config_files.sort();
+
+    for file in config_files {
+        let name = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Hidden files are not project configs
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if !project_index.projects.iter().any(|p| p.name == name) {
+            problems.push(Problem::OrphanedConfig { name, file });
+        }
+    }
+
+    problems
+}
+
+/// Repairs a problem, returns a description of what was done.
+fn repair(problem: &Problem, project_index: &mut ProjectIndex) -> Result<String, anyhow::Error> {
+    match problem {
+        Problem::DuplicatePath { path, names } => {
+            // The first registered project is kept
+            for name in &names[1..] {
+                project_index.remove_project_by_name(name)?;
+            }
+            Ok(format!("{} kept for {}", names[0], path))
+        }
+        Problem::MissingPath { name, path } => {
+            // Already removed as a duplicate
+            if project_index.find_project_by_name(name).is_err() {
+                return Ok(format!("{} already removed", name));
+            }
+
+            match relocate(path, project_index)? {
+                Some(new_path) => {
+                    project_index.update_project_and_save(name, |project| {
+                        project.path = new_path.clone();
+                    })?;
+                    Ok(format!("{} relocated to {}", name, new_path))
+                }
+                None => {
+                    project_index.remove_project_by_name(name)?;
+
+                    let config = crate::config::get_config().unwrap().read().unwrap();
+                    if config.settings.discovery.roots.is_empty() {
+                        Ok(format!(
+                            "{} removed, set discovery.roots in config.toml to relocate moved projects instead",
+                            name
+                        ))
+                    } else {
+                        Ok(format!(
+                            "{} removed, not found under the discovery roots",
+                            name
+                        ))
+                    }
+                }
+            }
+        }
+        Problem::MissingConfig { name } => {
+            let Ok(project) = project_index.find_project_by_name(name) else {
+                return Ok(format!("{} already removed", name));
+            };
+
+            // Relocating a project already regenerates its config
+            if !project_config_path(name).exists() {
+                ProjectConfig::new(project.clone()).save()?;
+            }
+            Ok(format!("{} config regenerated", name))
+        }
+        Problem::OrphanedConfig { name, file } => {
+            let project_config = match ProjectConfig::load(name) {
+                Ok(project_config) => project_config,
+                Err(e) => return set_aside(file, &format!("{:#}", e)),
+            };
+            let meta_data = &project_config.meta_data;
+
+            if meta_data.name != *name {
+                return set_aside(file, &format!("it describes {}", meta_data.name));
+            }
+            if !Path::new(&meta_data.path).is_dir() {
+                return set_aside(file, &format!("path {} doesn't exist", meta_data.path));
+            }
+            if let Err(e) = project_index.check_new_project(meta_data) {
+                return set_aside(file, &e.to_string());
+            }
+
+            project_index.add_project_and_save(project_config.meta_data)?;
+            Ok(format!("{} registered again", name))
+        }
+    }
+}
+
+/// Renames an orphaned config file which can't be registered again to `<name>.toml.orphan`,
+/// it may belong to an unmounted project or hold hand-made changes.
+fn set_aside(file: &Path, reason: &str) -> Result<String, anyhow::Error> {
+    let mut orphan_file = file.as_os_str().to_owned();
+    orphan_file.push(".orphan");
+    let orphan_file = PathBuf::from(orphan_file);
+
+    if orphan_file.exists() {
+        return Err(anyhow::anyhow!(
+            "{} already exists, remove it or restore it first",
+            orphan_file.display()
+        ));
+    }
+
+    fs::rename(file, &orphan_file)?;
+    Ok(format!(
+        "{} set aside as {}, {}",
+        file.display(),
+        orphan_file.display(),
+        reason
+    ))
+}
+
+/// Looks for a directory with the same name as the missing one under the discovery roots.
+/// A new path is returned only when exactly one unregistered candidate is found.
+fn relocate(
+    missing_path: &str,
+    project_index: &ProjectIndex,
+) -> Result<Option<String>, anyhow::Error> {
+    let Some(dir_name) = Path::new(missing_path).file_name() else {
+        return Ok(None);
+    };
+
+    let (roots, max_depth, ignore) = {
+        let config = crate::config::get_config().unwrap().read().unwrap();
+        let discovery = &config.settings.discovery;
+        (
+            discovery.root_paths(),
+            discovery.max_depth,
+            discovery.ignore.clone(),
+        )
+    };
+
+    let mut candidates = Vec::new();
+    for root in roots {
+        walk_dirs(&root, max_depth, &ignore, &mut |path| {
+            if path.file_name() == Some(dir_name) {
+                candidates.push(path.to_string_lossy().to_string());
+                WalkAction::SkipChildren
+            } else {
+                WalkAction::Continue
+            }
+        });
+    }
+
+    candidates.retain(|candidate| !project_index.projects.iter().any(|p| p.path == *candidate));
+    // Overlapping roots find the same directory several times
+    candidates.sort();
+    candidates.dedup();
+
+    match candidates.len() {
+        0 => Ok(None),
+        1 => Ok(candidates.pop()),
+        _ => Err(anyhow::anyhow!(
+            "Several candidates found ({}), use the move command",
+            candidates.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::models::project_config::ProjectMetaData;
+
+    use super::*;
+
+    #[test]
+    fn test_diagnose() {
+        let projects_dir = tempfile::tempdir().unwrap();
+        let project_dir = tempfile::tempdir().unwrap();
+        let missing_dir = project_dir.path().join("missing");
+
+        let mut project_index = ProjectIndex::new();
+        project_index.projects.push(ProjectMetaData {
+            path: project_dir.path().to_string_lossy().to_string(),
+            ..ProjectMetaData::sample("Healthy")
+        });
+        project_index.projects.push(ProjectMetaData {
+            path: project_dir.path().to_string_lossy().to_string(),
+            ..ProjectMetaData::sample("Duplicate")
+        });
+        project_index.projects.push(ProjectMetaData {
+            path: missing_dir.to_string_lossy().to_string(),
+            ..ProjectMetaData::sample("Missing")
+        });
+
+        for name in ["Healthy", "Duplicate", "Missing", "Orphan"] {
+            fs::write(projects_dir.path().join(format!("{}.toml", name)), "").unwrap();
+        }
+        fs::write(projects_dir.path().join(".Healthy.toml.edit"), "").unwrap();
+        fs::remove_file(projects_dir.path().join("Duplicate.toml")).unwrap();
+
+        let problems = diagnose(&project_index, projects_dir.path());
+
+        assert_eq!(
+            problems,
+            vec![
+                Problem::DuplicatePath {
+                    path: project_dir.path().to_string_lossy().to_string(),
+                    names: vec!["Healthy".to_string(), "Duplicate".to_string()],
+                },
+                Problem::MissingConfig {
+                    name: "Duplicate".to_string()
+                },
+                Problem::MissingPath {
+                    name: "Missing".to_string(),
+                    path: missing_dir.to_string_lossy().to_string(),
+                },
+                Problem::OrphanedConfig {
+                    name: "Orphan".to_string(),
+                    file: projects_dir.path().join("Orphan.toml"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnose_healthy_index() {
+        let projects_dir = tempfile::tempdir().unwrap();
+        let project_dir = tempfile::tempdir().unwrap();
+
+        let mut project_index = ProjectIndex::new();
+        project_index.projects.push(ProjectMetaData {
+            path: project_dir.path().to_string_lossy().to_string(),
+            ..ProjectMetaData::sample("Healthy")
+        });
+        fs::write(projects_dir.path().join("Healthy.toml"), "").unwrap();
+
+        assert!(diagnose(&project_index, projects_dir.path()).is_empty());
+    }
+}
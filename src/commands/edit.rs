@@ -0,0 +1,107 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::Command,
+};
+
+use anyhow::Context;
+
+use crate::models::{
+    project_config::{project_config_path, ProjectConfig, ProjectMetaData},
+    project_index::ProjectIndex,
+};
+
+pub fn execute(project_name: &str) {
+    if let Err(e) = edit(project_name) {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+fn edit(project_name: &str) -> Result<(), anyhow::Error> {
+    ProjectIndex::load_or_new().find_project_by_name(project_name)?;
+
+    let project_config = ProjectConfig::load(project_name)?;
+    let original_toml_str = project_config.to_toml_string()?;
+
+    // Work on a copy so an invalid config never replaces the current one
+    let edit_file_path =
+        project_config_path(project_name).with_file_name(format!(".{}.toml.edit", project_name));
+    fs::write(&edit_file_path, &original_toml_str).context("Failed to write the edit file")?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    let result = loop {
+        if let Err(e) = run_editor(&editor, &edit_file_path) {
+            break Err(e);
+        }
+
+        let toml_str =
+            fs::read_to_string(&edit_file_path).context("Failed to read the edit file")?;
+
+        if toml_str == original_toml_str {
+            println!("No changes");
+            break Ok(());
+        }
+
+        match validate(&toml_str, &project_config.meta_data) {
+            Ok(edited_config) => break edited_config.save(),
+            Err(e) => {
+                eprintln!("Invalid project config: {:?}", e);
+
+                if !confirm("Re-open the editor? [Y/n] ")? {
+                    break Err(anyhow::anyhow!("Changes discarded"));
+                }
+            }
+        }
+    };
+
+    let _ = fs::remove_file(&edit_file_path);
+    result
+}
+
+fn validate(toml_str: &str, meta_data: &ProjectMetaData) -> Result<ProjectConfig, anyhow::Error> {
+    let edited_config = ProjectConfig::from_toml_str(toml_str)?;
+
+    if edited_config.meta_data != *meta_data {
+        return Err(anyhow::anyhow!(
+            "meta_data is managed by the project index, use the rename, move, tag and alias commands"
+        ));
+    }
+
+    Ok(edited_config)
+}
+
+/// Runs the editor through the shell so `EDITOR="code --wait"` works.
+fn run_editor(editor: &str, file_path: &Path) -> Result<(), anyhow::Error> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(file_path)
+        .status()
+        .with_context(|| format!("Failed to run {}", editor))?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!("{} exited with {}", editor, status));
+    }
+    Ok(())
+}
+
+fn confirm(question: &str) -> Result<bool, anyhow::Error> {
+    eprint!("{}", question);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read input")?;
+
+    Ok(matches!(
+        answer.trim().to_lowercase().as_str(),
+        "" | "y" | "yes"
+    ))
+}
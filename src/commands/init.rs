@@ -0,0 +1,122 @@
+use clap::{CommandFactory, ValueEnum};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+pub fn execute(shell: InitShell, function_name: &str) {
+    print!("{}", render(shell, function_name, &passthrough_args()));
+}
+
+/// Arguments that are forwarded to the binary instead of opening a project.
+fn passthrough_args() -> Vec<String> {
+    let mut args: Vec<String> = crate::Cli::command()
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+
+    args.extend(
+        ["help", "-h", "--help", "-V", "--version"]
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+    args
+}
+
+fn render(shell: InitShell, function_name: &str, passthrough_args: &[String]) -> String {
+    let bin = env!("CARGO_BIN_NAME");
+
+    match shell {
+        InitShell::Bash | InitShell::Zsh => {
+            let rc_file = if shell == InitShell::Bash {
+                "~/.bashrc"
+            } else {
+                "~/.zshrc"
+            };
+
+            format!(
+                r#"# {bin} shell integration
+# Add the following line to {rc_file}:
+#   eval "$({bin} init {shell})"
+
+{function_name}() {{
+    case "$1" in
+        {cases})
+            command {bin} "$@"
+            ;;
+        *)
+            local project_dir
+            project_dir="$(command {bin} --resolve "$@")" || return $?
+            [ -n "$project_dir" ] && cd -- "$project_dir"
+            ;;
+    esac
+}}
+"#,
+                shell = if shell == InitShell::Bash {
+                    "bash"
+                } else {
+                    "zsh"
+                },
+                cases = passthrough_args.join("|"),
+            )
+        }
+        InitShell::Fish => format!(
+            r#"# {bin} shell integration
+# Add the following line to ~/.config/fish/config.fish:
+#   {bin} init fish | source
+
+function {function_name}
+    switch "$argv[1]"
+        case {cases}
+            command {bin} $argv
+        case '*'
+            set -l project_dir (command {bin} --resolve $argv)
+            or return $status
+            test -n "$project_dir"; and cd $project_dir
+    end
+end
+"#,
+            cases = passthrough_args.join(" "),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_passthrough_args_contains_subcommands() {
+        let args = passthrough_args();
+
+        assert!(args.contains(&"add".to_string()));
+        assert!(args.contains(&"init".to_string()));
+        assert!(args.contains(&"--help".to_string()));
+    }
+
+    #[test]
+    fn test_render_posix_shells() {
+        let args = vec!["add".to_string(), "list".to_string()];
+
+        for shell in [InitShell::Bash, InitShell::Zsh] {
+            let script = render(shell, "pm", &args);
+
+            assert!(script.contains("pm() {"));
+            assert!(script.contains("        add|list)"));
+            assert!(script.contains("--resolve \"$@\""));
+        }
+    }
+
+    #[test]
+    fn test_render_fish() {
+        let args = vec!["add".to_string(), "list".to_string()];
+        let script = render(InitShell::Fish, "p", &args);
+
+        assert!(script.contains("function p\n"));
+        assert!(script.contains("        case add list\n"));
+        assert!(script.contains("--resolve $argv"));
+    }
+}
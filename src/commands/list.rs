@@ -1,17 +1,330 @@
-use std::io::{self, Write};
+use std::{cmp::Reverse, path::Path};
 
-use crate::models::project_index::ProjectIndex;
+use chrono::Utc;
+use clap::ValueEnum;
+use cli_project_manager::lib::{git, utils};
+use glob::{MatchOptions, Pattern};
+use serde::Serialize;
 
-pub fn execute() {
+use crate::models::{project_config::ProjectMetaData, project_index::ProjectIndex};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ListSort {
+    Name,
+    /// Newest first
+    Created,
+    /// Most recently opened first
+    LastOpened,
+    /// Most used first
+    Frecency,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ListFormat {
+    #[default]
+    Table,
+    /// One name per line
+    Plain,
+    Json,
+    Toml,
+    Csv,
+}
+
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    /// Every type must match
+    pub types: Vec<String>,
+    /// Every tag must match
+    pub tags: Vec<String>,
+    /// Glob matched against the name, or against the path when it contains a `/`
+    pub filter: Option<String>,
+    /// Index order when not set
+    pub sort: Option<ListSort>,
+    /// Show every column of the table
+    pub long: bool,
+    pub format: ListFormat,
+}
+
+/// A project as printed by `list`, the metadata is flattened so scripts get every field
+#[derive(Serialize)]
+struct ListEntry<'a> {
+    #[serde(flatten)]
+    project: &'a ProjectMetaData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+}
+
+pub fn execute(options: &ListOptions) {
     let project_index = ProjectIndex::load_or_new();
 
-    if project_index.projects.is_empty() {
+    let pattern = options.filter.as_deref().map(|filter| {
+        Pattern::new(filter).unwrap_or_else(|e| {
+            eprintln!("Invalid filter {}: {}", filter, e);
+            std::process::exit(1);
+        })
+    });
+
+    let mut projects: Vec<&ProjectMetaData> = project_index
+        .projects
+        .iter()
+        .filter(|project| has_types(project, &options.types))
+        .filter(|project| options.tags.iter().all(|tag| project.tags.contains(tag)))
+        .filter(|project| pattern.as_ref().is_none_or(|p| matches_filter(project, p)))
+        .collect();
+
+    if let Some(sort) = options.sort {
+        sort_projects(&mut projects, sort);
+    }
+
+    if projects.is_empty() && options.format == ListFormat::Table {
         println!("No project found");
         return;
     }
 
-    let mut stdout = io::stdout();
+    let entries: Vec<ListEntry> = projects
+        .into_iter()
+        .map(|project| ListEntry {
+            project,
+            branch: git::current_branch(Path::new(&project.path)),
+        })
+        .collect();
+
+    let output = match options.format {
+        ListFormat::Table => Ok(render_table(&entries, options.long)),
+        ListFormat::Plain => Ok(entries
+            .iter()
+            .map(|entry| format!("{}\n", entry.project.name))
+            .collect()),
+        ListFormat::Json => serde_json::to_string_pretty(&entries)
+            .map(|json| format!("{}\n", json))
+            .map_err(anyhow::Error::from),
+        ListFormat::Toml => render_toml(&entries),
+        ListFormat::Csv => Ok(render_csv(&entries)),
+    };
+
+    match output {
+        Ok(output) => print!("{}", output),
+        Err(e) => {
+            eprintln!("Error formatting the projects: {:?}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn has_types(project: &ProjectMetaData, types: &[String]) -> bool {
+    types.iter().all(|wanted| {
+        project
+            .types
+            .iter()
+            .any(|project_type| project_type.eq_ignore_ascii_case(wanted))
+    })
+}
+
+fn matches_filter(project: &ProjectMetaData, pattern: &Pattern) -> bool {
+    let options = MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+
+    if pattern.as_str().contains('/') {
+        pattern.matches_with(&project.path, options)
+    } else {
+        pattern.matches_with(&project.name, options)
+    }
+}
+
+fn sort_projects(projects: &mut [&ProjectMetaData], sort: ListSort) {
+    let now = Utc::now();
+    let date = |date: Option<&str>| date.and_then(utils::parse_utc_date);
+
+    match sort {
+        ListSort::Name => projects.sort_by_key(|project| project.name.to_lowercase()),
+        ListSort::Created => {
+            projects.sort_by_key(|project| Reverse(date(Some(project.creation_date_utc.as_str()))))
+        }
+        ListSort::LastOpened => {
+            projects.sort_by_key(|project| Reverse(date(project.last_opened_utc.as_deref())))
+        }
+        ListSort::Frecency => projects.sort_by(|a, b| b.frecency(now).total_cmp(&a.frecency(now))),
+    }
+}
+
+fn render_table(entries: &[ListEntry], long: bool) -> String {
+    let now = Utc::now();
+    let mut headers = vec!["NAME", "TYPE", "TAGS", "BRANCH", "LAST OPENED", "PATH"];
+    if long {
+        headers.extend(["CREATED", "OPENS", "VERSION"]);
+    }
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            let project = entry.project;
+            let last_opened = project
+                .last_opened_utc
+                .as_deref()
+                .and_then(utils::parse_utc_date)
+                .map(|date| utils::format_elapsed(date, now))
+                .unwrap_or_else(|| "never".to_string());
+
+            let mut row = vec![
+                project.name.clone(),
+                project.types.join(","),
+                project.tags.join(","),
+                entry.branch.clone().unwrap_or_default(),
+                last_opened,
+                project.path.clone(),
+            ];
+
+            if long {
+                let created = utils::parse_utc_date(&project.creation_date_utc)
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| project.creation_date_utc.clone());
+                let version = project
+                    .package
+                    .as_ref()
+                    .and_then(|package| package.version.clone())
+                    .unwrap_or_default();
 
-    writeln!(stdout, "----- Projects -----").expect("Failed to write in stdout");
-    writeln!(stdout, "{}", project_index).expect("Failed to write to the stdout");
+                row.extend([created, project.open_count.to_string(), version]);
+            }
+            row
+        })
+        .collect();
+
+    utils::format_table(&headers, &rows)
+}
+
+fn render_toml(entries: &[ListEntry]) -> Result<String, anyhow::Error> {
+    #[derive(Serialize)]
+    struct Document<'a> {
+        projects: &'a [ListEntry<'a>],
+    }
+
+    // Going through `toml::Value` puts the nested tables after the plain values
+    let value = toml::Value::try_from(Document { projects: entries })?;
+    Ok(toml::to_string(&value)?)
+}
+
+fn render_csv(entries: &[ListEntry]) -> String {
+    let mut csv = String::from(
+        "name,path,types,tags,branch,last_opened_utc,open_count,creation_date_utc,package_name,package_version\n",
+    );
+
+    for entry in entries {
+        let project = entry.project;
+        let package = project.package.clone().unwrap_or_default();
+        let fields = [
+            project.name.clone(),
+            project.path.clone(),
+            project.types.join(";"),
+            project.tags.join(";"),
+            entry.branch.clone().unwrap_or_default(),
+            project.last_opened_utc.clone().unwrap_or_default(),
+            project.open_count.to_string(),
+            project.creation_date_utc.clone(),
+            package.name.unwrap_or_default(),
+            package.version.unwrap_or_default(),
+        ];
+
+        let line: Vec<String> = fields.iter().map(|field| csv_escape(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_has_types() {
+        let project = ProjectMetaData {
+            types: vec!["rust".to_string(), "node".to_string()],
+            ..ProjectMetaData::sample("api")
+        };
+
+        assert!(has_types(&project, &[]));
+        assert!(has_types(&project, &["Rust".to_string()]));
+        assert!(has_types(
+            &project,
+            &["rust".to_string(), "node".to_string()]
+        ));
+        assert!(!has_types(
+            &project,
+            &["rust".to_string(), "go".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        let project = ProjectMetaData {
+            path: "/srv/work/Backend-API".to_string(),
+            ..ProjectMetaData::sample("Backend-API")
+        };
+
+        assert!(matches_filter(&project, &Pattern::new("backend*").unwrap()));
+        assert!(!matches_filter(&project, &Pattern::new("front*").unwrap()));
+        assert!(matches_filter(
+            &project,
+            &Pattern::new("/srv/*/Backend-*").unwrap()
+        ));
+        assert!(!matches_filter(&project, &Pattern::new("/home/*").unwrap()));
+    }
+
+    #[test]
+    fn test_sort_projects() {
+        let now = Utc::now();
+        let mut alpha = ProjectMetaData::sample("alpha");
+        let mut beta = ProjectMetaData::sample("Beta");
+        let gamma = ProjectMetaData::sample("gamma");
+
+        alpha.creation_date_utc = "2024-01-01T00:00:00Z".to_string();
+        alpha.last_opened_utc = Some((now - chrono::Duration::days(3)).to_string());
+        alpha.open_count = 50;
+        beta.last_opened_utc = Some(now.to_string());
+        beta.open_count = 2;
+
+        let names = |sort: ListSort| {
+            let mut projects = vec![&gamma, &beta, &alpha];
+            sort_projects(&mut projects, sort);
+            projects.iter().map(|p| p.name.as_str()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(ListSort::Name), vec!["alpha", "Beta", "gamma"]);
+        assert_eq!(names(ListSort::Created), vec!["alpha", "gamma", "Beta"]);
+        assert_eq!(names(ListSort::LastOpened), vec!["Beta", "alpha", "gamma"]);
+        assert_eq!(names(ListSort::Frecency), vec!["alpha", "Beta", "gamma"]);
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let project = ProjectMetaData {
+            path: "/srv/work/api, v2".to_string(),
+            types: vec!["rust".to_string()],
+            tags: vec!["work".to_string(), "oss".to_string()],
+            ..ProjectMetaData::sample("api, v2")
+        };
+        let entries = vec![ListEntry {
+            project: &project,
+            branch: Some("main".to_string()),
+        }];
+
+        let csv = render_csv(&entries);
+
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            r#""api, v2","/srv/work/api, v2",rust,work;oss,main,,0,2023-09-23T12:00:00Z,,"#
+        );
+    }
 }
@@ -0,0 +1,134 @@
+use crate::models::{project_config::ProjectMetaData, project_index::ProjectIndex};
+
+/// A list of single words stored on every project, edited by the `tag` and `alias` commands
+pub trait ListField {
+    /// Singular and plural of the field, used in messages
+    const NAME: &'static str;
+    const PLURAL: &'static str;
+    /// Printed after the project name once values are added or removed, like `tagged` and `untagged`
+    const ADDED: &'static str;
+    const REMOVED: &'static str;
+
+    fn values(project: &ProjectMetaData) -> &Vec<String>;
+    fn values_mut(project: &mut ProjectMetaData) -> &mut Vec<String>;
+
+    /// Error when removing a value the project doesn't have
+    fn missing(project_name: &str, value: &str) -> anyhow::Error;
+
+    /// Checks specific to the field, on top of the ones of `validate`
+    fn check(_value: &str) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+}
+
+/// Values are single words so they can be typed in queries and filters
+pub fn validate<F: ListField>(value: &str) -> Result<(), anyhow::Error> {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == ',' || c == ':') {
+        return Err(anyhow::anyhow!(
+            "Invalid {} `{}`, {} can't be empty or contain spaces, commas or colons",
+            F::NAME,
+            value,
+            F::PLURAL
+        ));
+    }
+    F::check(value)
+}
+
+pub fn add<F: ListField>(project_name: &str, values: &[String]) -> Result<(), anyhow::Error> {
+    for value in values {
+        validate::<F>(value)?;
+    }
+
+    let mut project_index = ProjectIndex::load_for_update()?;
+    project_index.update_project_and_save(project_name, |project| {
+        let field = F::values_mut(project);
+        for value in values {
+            if !field.contains(value) {
+                field.push(value.clone());
+            }
+        }
+        field.sort();
+    })?;
+
+    println!("{} {} {}", project_name, F::ADDED, values.join(", "));
+    Ok(())
+}
+
+pub fn remove<F: ListField>(project_name: &str, values: &[String]) -> Result<(), anyhow::Error> {
+    let mut project_index = ProjectIndex::load_for_update()?;
+
+    let project = project_index.find_project_by_name(project_name)?;
+    if let Some(missing) = values
+        .iter()
+        .find(|value| !F::values(project).contains(value))
+    {
+        return Err(F::missing(project_name, missing));
+    }
+
+    project_index.update_project_and_save(project_name, |project| {
+        F::values_mut(project).retain(|value| !values.contains(value));
+    })?;
+
+    println!("{} {} {}", project_name, F::REMOVED, values.join(", "));
+    Ok(())
+}
+
+/// Prints the values of a project, one per line
+pub fn list_project<F: ListField>(project_name: &str) -> Result<(), anyhow::Error> {
+    let project_index = ProjectIndex::load_or_new();
+
+    for value in F::values(project_index.find_project_by_name(project_name)?) {
+        println!("{}", value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Labels;
+
+    impl ListField for Labels {
+        const NAME: &'static str = "label";
+        const PLURAL: &'static str = "labels";
+        const ADDED: &'static str = "labeled";
+        const REMOVED: &'static str = "unlabeled";
+
+        fn values(project: &ProjectMetaData) -> &Vec<String> {
+            &project.tags
+        }
+
+        fn values_mut(project: &mut ProjectMetaData) -> &mut Vec<String> {
+            &mut project.tags
+        }
+
+        fn missing(project_name: &str, value: &str) -> anyhow::Error {
+            anyhow::anyhow!("{} is not labeled {}", project_name, value)
+        }
+
+        fn check(value: &str) -> Result<(), anyhow::Error> {
+            match value {
+                "reserved" => Err(anyhow::anyhow!("`reserved` is reserved")),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate::<Labels>("client-x").is_ok());
+        assert!(validate::<Labels>("oss").is_ok());
+        assert!(validate::<Labels>("").is_err());
+        assert!(validate::<Labels>("two words").is_err());
+        assert!(validate::<Labels>("a,b").is_err());
+        assert!(validate::<Labels>("tag:rust").is_err());
+        assert!(validate::<Labels>("reserved").is_err());
+
+        let message = validate::<Labels>("a b").unwrap_err().to_string();
+        assert_eq!(
+            message,
+            "Invalid label `a b`, labels can't be empty or contain spaces, commas or colons"
+        );
+    }
+}
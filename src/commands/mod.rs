@@ -1,4 +1,22 @@
 pub mod add;
+pub mod alias;
 pub mod base;
+pub mod clone;
+pub mod completions;
+pub mod config;
 pub mod delete;
+pub mod doctor;
+pub mod edit;
+pub mod init;
 pub mod list;
+pub mod list_field;
+pub mod move_project;
+pub mod new;
+pub mod picker;
+pub mod query;
+pub mod rename;
+pub mod repair;
+pub mod scan;
+pub mod show;
+pub mod status;
+pub mod tag;
@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::models::project_index::ProjectIndex;
+
+pub fn execute(project_name: &str, new_path: &Path) {
+    let new_path = match new_path
+        .canonicalize()
+        .context("Failed to canonicalize path")
+    {
+        Ok(path) => path.to_string_lossy().to_string(),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut project_index = ProjectIndex::load_for_update().unwrap_or_else(|e| {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    });
+
+    if let Err(e) = project_index.update_project_and_save(project_name, |project| {
+        project.path = new_path.clone();
+    }) {
+        eprintln!("Error moving project: {:?}", e);
+        std::process::exit(1);
+    }
+
+    println!("{project_name} moved to {new_path}");
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use anyhow::Context;
+use chrono::Utc;
+
+use crate::models::{
+    hooks::{HookStage, Hooks},
+    project_index::{self, ProjectIndex},
+    template::Template,
+};
+
+pub struct NewOptions {
+    /// Defaults to `./<name>`
+    pub dest: Option<PathBuf>,
+    /// Run `git init` even if the template doesn't ask for it
+    pub git_init: bool,
+}
+
+/// Creates a project from a template, registers it then runs the `on_create` hooks.
+pub fn execute(template_name: &str, project_name: &str, options: &NewOptions) {
+    if let Err(e) = create(template_name, project_name, options) {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+fn create(
+    template_name: &str,
+    project_name: &str,
+    options: &NewOptions,
+) -> Result<(), anyhow::Error> {
+    // Checked before the name is used as a directory name
+    project_index::check_name(project_name)?;
+
+    let template = Template::load(template_name)?;
+    let destination = match &options.dest {
+        Some(dest) => dest.clone(),
+        None => env::current_dir()
+            .context("Failed to get the current directory")?
+            .join(project_name),
+    };
+
+    if destination.exists() {
+        return Err(anyhow::anyhow!("{} already exists", destination.display()));
+    }
+    ProjectIndex::load_or_new().ensure_name_available(project_name)?;
+
+    // A half copied template is useless, it is removed
+    if let Err(e) = template.render(&destination, &variables(project_name)) {
+        let _ = fs::remove_dir_all(&destination);
+        return Err(e.context(format!(
+            "Failed to create the project from {}",
+            template.name
+        )));
+    }
+
+    // Like a failed rendering, a project which can't be registered is removed
+    let registered = (|| {
+        if options.git_init || template.config.git_init {
+            git_init(&destination)?;
+        }
+        crate::commands::add::register(&destination, &Some(project_name.to_string()))
+    })();
+    let project_config = match registered {
+        Ok(project_config) => project_config,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&destination);
+            return Err(e);
+        }
+    };
+
+    crate::commands::add::run_hooks(&project_config);
+    let global_hooks = crate::config::get_config()
+        .unwrap()
+        .read()
+        .unwrap()
+        .settings
+        .hooks
+        .clone();
+    let template_hooks = Hooks {
+        on_create: template.config.post_create.clone(),
+        abort_on_failure: true,
+        ..Default::default()
+    };
+
+    if let Err(e) = Hooks::merge(&global_hooks, &template_hooks).run(
+        HookStage::Create,
+        Path::new(&project_config.meta_data.path),
+        &project_config.environment(),
+        false,
+    ) {
+        crate::commands::add::exit_registered_anyway(e, &project_config.meta_data.name);
+    }
+    Ok(())
+}
+
+/// Values of the `{{name}}`, `{{date}}` and `{{author}}` placeholders
+fn variables(project_name: &str) -> BTreeMap<&'static str, String> {
+    BTreeMap::from([
+        ("name", project_name.to_string()),
+        ("date", Utc::now().format("%Y-%m-%d").to_string()),
+        ("author", author()),
+    ])
+}
+
+/// The git user name, or the login name when git isn't configured
+fn author() -> String {
+    Command::new("git")
+        .args(["config", "user.name"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| env::var("USER").ok())
+        .unwrap_or_default()
+}
+
+fn git_init(path: &Path) -> Result<(), anyhow::Error> {
+    let status = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(path)
+        .status()
+        .context("Failed to run git init")?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!("git init exited with {}", status));
+    }
+    Ok(())
+}
@@ -0,0 +1,524 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::Path,
+};
+
+use cli_project_manager::lib::{fuzzing_matching, git};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+
+use crate::models::project_config::ProjectMetaData;
+
+/// Number of README lines shown in the preview
+const README_PREVIEW_LINES: usize = 10;
+/// Narrower terminals don't get a preview pane
+const PREVIEW_MIN_WIDTH: u16 = 60;
+
+/// Lets the user pick one of `projects`, `None` when the selection is cancelled.
+///
+/// An interactive fuzzy finder is drawn on stderr when stdin and stderr are terminals,
+/// so it also works when stdout is read by the shell integration. Otherwise a numbered
+/// list is printed and the choice is read from stdin.
+pub fn pick<'a>(
+    projects: &[&'a ProjectMetaData],
+) -> Result<Option<&'a ProjectMetaData>, anyhow::Error> {
+    if projects.is_empty() {
+        return Ok(None);
+    }
+
+    if io::stdin().is_terminal() && io::stderr().is_terminal() {
+        pick_interactive(projects)
+    } else {
+        pick_numbered(projects, &mut io::stdin().lock(), &mut io::stderr())
+    }
+}
+
+fn pick_numbered<'a, R, W>(
+    projects: &[&'a ProjectMetaData],
+    input: &mut R,
+    output: &mut W,
+) -> Result<Option<&'a ProjectMetaData>, anyhow::Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(output, "Select a project:")?;
+    for (i, project) in projects.iter().enumerate() {
+        writeln!(output, "{}: {}", i, project.name)?;
+    }
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        match parse_selection(line.trim(), projects) {
+            Some(index) => return Ok(Some(projects[index])),
+            None => writeln!(
+                output,
+                "Invalid selection, enter a number between 0 and {} or a project name",
+                projects.len() - 1
+            )?,
+        }
+    }
+}
+
+/// Accepts the number of a project or its exact name
+fn parse_selection(input: &str, projects: &[&ProjectMetaData]) -> Option<usize> {
+    match input.parse::<usize>() {
+        Ok(index) => (index < projects.len()).then_some(index),
+        Err(_) => projects.iter().position(|project| project.name == input),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum PickerAction {
+    Continue,
+    Select(usize),
+    Cancel,
+}
+
+struct PickerState<'a> {
+    projects: &'a [&'a ProjectMetaData],
+    query: String,
+    /// Index in `projects` and positions of the matched characters of the name, best match first
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+    /// First match shown, for scrolling
+    offset: usize,
+}
+
+impl<'a> PickerState<'a> {
+    fn new(projects: &'a [&'a ProjectMetaData]) -> Self {
+        let mut state = PickerState {
+            projects,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            offset: 0,
+        };
+        state.refresh();
+        state
+    }
+
+    fn refresh(&mut self) {
+        self.matches = filter_projects(self.projects, &self.query);
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    fn selected_project(&self) -> Option<usize> {
+        self.matches.get(self.selected).map(|(index, _)| *index)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+
+        let last = self.matches.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+    }
+
+    /// Keeps the selected match inside the `height` visible lines
+    fn scroll(&mut self, height: usize) {
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> PickerAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => return PickerAction::Cancel,
+            KeyCode::Char('c') | KeyCode::Char('g') if ctrl => return PickerAction::Cancel,
+            KeyCode::Enter => {
+                return match self.selected_project() {
+                    Some(index) => PickerAction::Select(index),
+                    None => PickerAction::Continue,
+                }
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Tab => self.move_selection(1),
+            KeyCode::Char('p') | KeyCode::Char('k') if ctrl => self.move_selection(-1),
+            KeyCode::Char('n') | KeyCode::Char('j') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.refresh();
+            }
+            KeyCode::Backspace if self.query.pop().is_some() => self.refresh(),
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refresh();
+            }
+            _ => {}
+        }
+
+        PickerAction::Continue
+    }
+}
+
+/// Projects whose name contains `query` as a subsequence, best score first.
+/// Every project is kept, in order, when the query is empty.
+fn filter_projects(projects: &[&ProjectMetaData], query: &str) -> Vec<(usize, Vec<usize>)> {
+    if query.is_empty() {
+        return (0..projects.len())
+            .map(|index| (index, Vec::new()))
+            .collect();
+    }
+
+    let mut matches: Vec<(u32, usize, Vec<usize>)> = projects
+        .iter()
+        .enumerate()
+        .map(|(index, project)| {
+            let fuzzy_match = fuzzing_matching::fuzzy_match(&project.name, query);
+            (fuzzy_match.score, index, fuzzy_match.positions)
+        })
+        // Typo matches have no positions, they would be confusing while typing
+        .filter(|(_, _, positions)| !positions.is_empty())
+        .collect();
+
+    // Stable sort, equal scores keep the given order
+    matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+
+    matches
+        .into_iter()
+        .map(|(_, index, positions)| (index, positions))
+        .collect()
+}
+
+/// Restores the terminal even when drawing fails
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = TerminalGuard;
+        queue!(io::stderr(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        io::stderr().flush()?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = queue!(io::stderr(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = io::stderr().flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn pick_interactive<'a>(
+    projects: &[&'a ProjectMetaData],
+) -> Result<Option<&'a ProjectMetaData>, anyhow::Error> {
+    let mut state = PickerState::new(projects);
+    let mut previews: HashMap<usize, Vec<String>> = HashMap::new();
+    let _guard = TerminalGuard::enter()?;
+
+    loop {
+        let (width, height) = match terminal::size()? {
+            // Some pseudo terminals don't report their size
+            (0, _) | (_, 0) => (80, 24),
+            size => size,
+        };
+        state.scroll(height.saturating_sub(2) as usize);
+
+        let preview = if width >= PREVIEW_MIN_WIDTH {
+            state.selected_project().map(|index| {
+                previews
+                    .entry(index)
+                    .or_insert_with(|| preview_lines(projects[index]))
+                    .clone()
+            })
+        } else {
+            None
+        };
+
+        render(&state, preview.as_deref(), width, height, &mut io::stderr())?;
+
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match state.handle_key(key) {
+                PickerAction::Continue => {}
+                PickerAction::Select(index) => return Ok(Some(projects[index])),
+                PickerAction::Cancel => return Ok(None),
+            },
+            _ => {}
+        }
+    }
+}
+
+fn render<W: Write>(
+    state: &PickerState,
+    preview: Option<&[String]>,
+    width: u16,
+    height: u16,
+    out: &mut W,
+) -> io::Result<()> {
+    let list_width = if preview.is_some() {
+        (width as usize * 45 / 100).max(20)
+    } else {
+        width as usize
+    };
+    let list_height = height.saturating_sub(2) as usize;
+
+    queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    queue!(
+        out,
+        Print(truncate(&format!("> {}", state.query), width as usize))
+    )?;
+    queue!(
+        out,
+        cursor::MoveTo(0, 1),
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!(
+            "  {}/{}",
+            state.matches.len(),
+            state.projects.len()
+        )),
+        ResetColor
+    )?;
+
+    let visible = state.matches.iter().enumerate().skip(state.offset);
+    for (row, (match_index, (project_index, positions))) in visible.take(list_height).enumerate() {
+        let selected = match_index == state.selected;
+        queue!(out, cursor::MoveTo(0, row as u16 + 2))?;
+
+        if selected {
+            queue!(out, SetAttribute(Attribute::Reverse), Print("> "))?;
+        } else {
+            queue!(out, Print("  "))?;
+        }
+
+        let name = &state.projects[*project_index].name;
+        for (i, c) in name.chars().take(list_width.saturating_sub(2)).enumerate() {
+            if positions.contains(&i) {
+                queue!(out, SetForegroundColor(Color::Green), Print(c), ResetColor)?;
+                if selected {
+                    // ResetColor also resets the attributes on some terminals
+                    queue!(out, SetAttribute(Attribute::Reverse))?;
+                }
+            } else {
+                queue!(out, Print(c))?;
+            }
+        }
+
+        queue!(out, SetAttribute(Attribute::Reset))?;
+    }
+
+    if let Some(preview) = preview {
+        let preview_column = list_width as u16 + 1;
+        let preview_width = (width as usize).saturating_sub(list_width + 3);
+
+        for row in 0..height {
+            queue!(
+                out,
+                cursor::MoveTo(preview_column, row),
+                SetForegroundColor(Color::DarkGrey),
+                Print("│"),
+                ResetColor
+            )?;
+        }
+
+        for (row, line) in preview.iter().take(height as usize).enumerate() {
+            queue!(
+                out,
+                cursor::MoveTo(preview_column + 2, row as u16),
+                Print(truncate(line, preview_width))
+            )?;
+        }
+    }
+
+    out.flush()
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+/// Path, types, tags, git status and the head of the README of a project
+fn preview_lines(project: &ProjectMetaData) -> Vec<String> {
+    let path = Path::new(&project.path);
+    let mut lines = vec![format!("Path: {}", project.path)];
+
+    if !project.types.is_empty() {
+        lines.push(format!("Type: {}", project.types.join(", ")));
+    }
+    if !project.tags.is_empty() {
+        lines.push(format!("Tags: {}", project.tags.join(", ")));
+    }
+    if let Some(status) = git::status(path) {
+        let changes = match status.changes {
+            0 => "clean".to_string(),
+            count => format!("{} changed file(s)", count),
+        };
+        lines.push(format!("Git:  {} ({})", status.branch, changes));
+    }
+
+    if let Some(readme) = [
+        "README.md",
+        "README",
+        "readme.md",
+        "README.rst",
+        "README.txt",
+    ]
+    .iter()
+    .find_map(|name| fs::read_to_string(path.join(name)).ok())
+    {
+        lines.push(String::new());
+        lines.extend(
+            readme
+                .lines()
+                .take(README_PREVIEW_LINES)
+                .map(|line| line.to_string()),
+        );
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_filter_projects() {
+        let (api, web, backend) = (
+            ProjectMetaData::sample("api"),
+            ProjectMetaData::sample("web"),
+            ProjectMetaData::sample("backend-api"),
+        );
+        let projects = vec![&api, &web, &backend];
+
+        assert_eq!(filter_projects(&projects, "").len(), 3);
+        assert_eq!(
+            filter_projects(&projects, "api"),
+            vec![(0, vec![0, 1, 2]), (2, vec![8, 9, 10])]
+        );
+        assert!(filter_projects(&projects, "xyz").is_empty());
+    }
+
+    #[test]
+    fn test_picker_keys() {
+        let (api, web, backend) = (
+            ProjectMetaData::sample("api"),
+            ProjectMetaData::sample("web"),
+            ProjectMetaData::sample("backend-api"),
+        );
+        let projects = vec![&api, &web, &backend];
+        let mut state = PickerState::new(&projects);
+
+        assert_eq!(state.handle_key(key(KeyCode::Down)), PickerAction::Continue);
+        assert_eq!(state.handle_key(key(KeyCode::Down)), PickerAction::Continue);
+        assert_eq!(state.handle_key(key(KeyCode::Down)), PickerAction::Continue);
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            PickerAction::Select(2)
+        );
+
+        // Typing filters and resets the selection
+        state.handle_key(key(KeyCode::Char('w')));
+        assert_eq!(state.matches.len(), 1);
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            PickerAction::Select(1)
+        );
+
+        state.handle_key(key(KeyCode::Char('z')));
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            PickerAction::Continue
+        );
+        state.handle_key(key(KeyCode::Backspace));
+        assert_eq!(state.query, "w");
+
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(state.handle_key(ctrl_c), PickerAction::Cancel);
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), PickerAction::Cancel);
+    }
+
+    #[test]
+    fn test_picker_scroll() {
+        let names: Vec<ProjectMetaData> = (0..10)
+            .map(|i| ProjectMetaData::sample(&format!("p{}", i)))
+            .collect();
+        let projects: Vec<&ProjectMetaData> = names.iter().collect();
+        let mut state = PickerState::new(&projects);
+
+        state.move_selection(7);
+        state.scroll(5);
+        assert_eq!(state.offset, 3);
+
+        state.move_selection(-6);
+        state.scroll(5);
+        assert_eq!(state.offset, 1);
+    }
+
+    #[test]
+    fn test_pick_numbered() {
+        let (api, web) = (
+            ProjectMetaData::sample("api"),
+            ProjectMetaData::sample("web"),
+        );
+        let projects = vec![&api, &web];
+        let mut output = Vec::new();
+
+        let mut input = Cursor::new("7\nnope\n1\n");
+        let selected = pick_numbered(&projects, &mut input, &mut output).unwrap();
+        assert_eq!(selected.unwrap().name, "web");
+        assert!(String::from_utf8_lossy(&output).contains("Invalid selection"));
+
+        let mut input = Cursor::new("api\n");
+        let selected = pick_numbered(&projects, &mut input, &mut output).unwrap();
+        assert_eq!(selected.unwrap().name, "api");
+
+        // End of input cancels
+        let mut input = Cursor::new("");
+        assert!(pick_numbered(&projects, &mut input, &mut output)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_preview_lines() {
+        let project_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            project_dir.path().join("README.md"),
+            "# My project\n\nDoes things\n",
+        )
+        .unwrap();
+
+        let project = ProjectMetaData {
+            path: project_dir.path().to_string_lossy().to_string(),
+            tags: vec!["work".to_string()],
+            ..ProjectMetaData::sample("api")
+        };
+
+        let lines = preview_lines(&project);
+
+        assert_eq!(lines[0], format!("Path: {}", project.path));
+        assert_eq!(lines[1], "Tags: work");
+        assert_eq!(&lines[3..], ["# My project", "", "Does things"]);
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::Path;
+
+use cli_project_manager::lib::{fuzzing_matching::fuzzy_match, utils};
+
+use crate::models::project_config::ProjectMetaData;
+
+/// A project query: `tag:` and `path:` filters narrowing the projects, the remaining words
+/// form the fuzzy pattern, e.g. `tag:rust api` or `path:work/ api`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Query {
+    pub tags: Vec<String>,
+    pub paths: Vec<String>,
+    pub pattern: String,
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Query {
+        let mut parsed = Query::default();
+        let mut words = Vec::new();
+
+        for word in query.split_whitespace() {
+            if let Some(tag) = word.strip_prefix("tag:").filter(|tag| !tag.is_empty()) {
+                parsed.tags.push(tag.to_string());
+            } else if let Some(path) = word.strip_prefix("path:").filter(|path| !path.is_empty()) {
+                parsed.paths.push(path.to_string());
+            } else {
+                words.push(word);
+            }
+        }
+
+        parsed.pattern = words.join(" ");
+        parsed
+    }
+
+    pub fn has_filters(&self) -> bool {
+        !self.tags.is_empty() || !self.paths.is_empty()
+    }
+
+    /// A project passes when it has every tag and its path contains every `path:` value
+    pub fn accepts(&self, project: &ProjectMetaData) -> bool {
+        self.tags.iter().all(|tag| project.tags.contains(tag))
+            && self.paths.iter().all(|path| {
+                let path = utils::expand_tilde(path);
+                project.path.contains(path.to_string_lossy().as_ref())
+            })
+    }
+}
+
+/// Weights of the path segments and tags matches, relative to the name ones
+#[derive(Debug, Clone, Copy)]
+pub struct MatchWeights {
+    pub path: f64,
+    pub tag: f64,
+}
+
+/// Scores a project against `pattern` from 0 to 100, keeping its best field.
+///
+/// The name and the aliases count fully, path segments and tags are weighted down.
+/// Those only count when they contain the pattern, a typo in a directory name isn't a match.
+pub fn score_project(project: &ProjectMetaData, pattern: &str, weights: MatchWeights) -> u32 {
+    let names = std::iter::once(&project.name).chain(&project.aliases);
+    let mut score = names
+        .map(|name| fuzzy_match(name, pattern).score)
+        .max()
+        .unwrap_or_default();
+
+    let weighted = |text: &str, weight: f64| {
+        let fuzzy_match = fuzzy_match(text, pattern);
+        if weight <= 0.0 || fuzzy_match.positions.is_empty() {
+            return 0;
+        }
+        (f64::from(fuzzy_match.score) * weight).round() as u32
+    };
+
+    for tag in &project.tags {
+        score = score.max(weighted(tag, weights.tag));
+    }
+    for segment in path_segments(&project.path) {
+        score = score.max(weighted(&segment, weights.path));
+    }
+
+    score
+}
+
+/// Directory names of the path, below the home directory when the project lives in it
+fn path_segments(path: &str) -> Vec<String> {
+    let path = Path::new(path);
+    let relative_path = dirs::home_dir()
+        .and_then(|home| path.strip_prefix(home).ok())
+        .unwrap_or(path);
+
+    relative_path
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(segment) => Some(segment.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WEIGHTS: MatchWeights = MatchWeights {
+        path: 0.5,
+        tag: 0.7,
+    };
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            Query::parse("tag:rust  path:work/ back end"),
+            Query {
+                tags: vec!["rust".to_string()],
+                paths: vec!["work/".to_string()],
+                pattern: "back end".to_string(),
+            }
+        );
+
+        // Empty filters are plain words
+        let query = Query::parse("tag: api");
+        assert!(!query.has_filters());
+        assert_eq!(query.pattern, "tag: api");
+
+        assert_eq!(Query::parse(""), Query::default());
+    }
+
+    #[test]
+    fn test_query_accepts() {
+        let service = ProjectMetaData {
+            path: "/srv/work/service".to_string(),
+            tags: vec!["rust".to_string(), "oss".to_string()],
+            ..ProjectMetaData::sample("service")
+        };
+        let website = ProjectMetaData {
+            path: "/srv/perso/website".to_string(),
+            tags: vec!["node".to_string()],
+            ..ProjectMetaData::sample("website")
+        };
+
+        let query = Query::parse("tag:rust");
+        assert!(query.accepts(&service));
+        assert!(!query.accepts(&website));
+
+        let query = Query::parse("path:work/ tag:oss");
+        assert!(query.accepts(&service));
+        assert!(!query.accepts(&website));
+
+        let query = Query::parse("path:work/ tag:node");
+        assert!(!query.accepts(&service));
+        assert!(Query::parse("site").accepts(&website));
+    }
+
+    #[test]
+    fn test_score_project() {
+        let service = ProjectMetaData {
+            path: "/srv/clients/acme/backend-service".to_string(),
+            tags: vec!["payments".to_string()],
+            aliases: vec!["api".to_string()],
+            ..ProjectMetaData::sample("backend-service")
+        };
+
+        // Aliases score like the name
+        assert_eq!(
+            score_project(&service, "api", WEIGHTS),
+            fuzzy_match("api", "api").score
+        );
+
+        // Path segments and tags score below the name
+        let path_score = score_project(&service, "acme", WEIGHTS);
+        assert_eq!(
+            path_score,
+            (f64::from(fuzzy_match("acme", "acme").score) * 0.5).round() as u32
+        );
+        let tag_score = score_project(&service, "payments", WEIGHTS);
+        assert_eq!(
+            tag_score,
+            (f64::from(fuzzy_match("payments", "payments").score) * 0.7).round() as u32
+        );
+        assert!(score_project(&service, "backend", WEIGHTS) > tag_score);
+
+        // Disabled weights ignore the secondary fields
+        let name_only = MatchWeights {
+            path: 0.0,
+            tag: 0.0,
+        };
+        assert!(score_project(&service, "acme", name_only) < path_score);
+    }
+
+    #[test]
+    fn test_path_segments() {
+        assert_eq!(
+            path_segments("/srv/work/service"),
+            vec!["srv", "work", "service"]
+        );
+
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(
+            path_segments(&home.join("code/service").to_string_lossy()),
+            vec!["code", "service"]
+        );
+    }
+}
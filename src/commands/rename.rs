@@ -0,0 +1,17 @@
+use crate::models::project_index::ProjectIndex;
+
+pub fn execute(project_name: &str, new_name: &str) {
+    let mut project_index = ProjectIndex::load_for_update().unwrap_or_else(|e| {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    });
+
+    if let Err(e) = project_index.update_project_and_save(project_name, |project| {
+        project.name = new_name.to_string();
+    }) {
+        eprintln!("Error renaming project: {:?}", e);
+        std::process::exit(1);
+    }
+
+    println!("{project_name} renamed to {new_name}");
+}
@@ -0,0 +1,25 @@
+use crate::models::project_index::ProjectIndex;
+
+/// Salvages the projects of a corrupt index, the lost ones can be recovered from their config file with `doctor --fix`.
+pub fn execute() {
+    let report = ProjectIndex::repair().unwrap_or_else(|e| {
+        eprintln!("Error repairing the project index: {:?}", e);
+        std::process::exit(1);
+    });
+
+    let Some(corrupt_copy) = report.corrupt_copy else {
+        println!("The project index is valid, nothing to repair");
+        return;
+    };
+
+    println!(
+        "{} project(s) salvaged, {} entry(ies) discarded",
+        report.salvaged.len(),
+        report.discarded
+    );
+    println!("The corrupt index was kept in {}", corrupt_copy.display());
+
+    if report.discarded > 0 {
+        println!("Run `doctor --fix` to register again the discarded projects which still have a config file");
+    }
+}
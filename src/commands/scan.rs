@@ -0,0 +1,212 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use cli_project_manager::lib::walk::{walk_dirs, WalkAction};
+
+use crate::models::{
+    project_config::{ProjectConfig, ProjectMetaData},
+    project_index::ProjectIndex,
+};
+
+pub struct ScanOptions {
+    pub depth: Option<usize>,
+    pub dry_run: bool,
+}
+
+/// Registers every project found under `roots`, the discovery roots of the settings are used when empty.
+/// `on_add` hooks are not run for scanned projects.
+pub fn execute(roots: &[PathBuf], options: &ScanOptions) {
+    let (roots, max_depth, markers, ignore) = {
+        let config = crate::config::get_config().unwrap().read().unwrap();
+        let discovery = &config.settings.discovery;
+        let roots = if roots.is_empty() {
+            discovery.root_paths()
+        } else {
+            roots.to_vec()
+        };
+        (
+            roots,
+            options.depth.unwrap_or(discovery.max_depth),
+            discovery.markers.clone(),
+            discovery.ignore.clone(),
+        )
+    };
+
+    if roots.is_empty() {
+        eprintln!("No root to scan, pass one or set `discovery.roots` in the config file");
+        std::process::exit(1);
+    }
+
+    let mut project_index = ProjectIndex::load_for_update().unwrap_or_else(|e| {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    });
+    // Aliases open projects too, a scanned project can't be named like one
+    let mut taken_names: HashSet<String> = project_index
+        .projects
+        .iter()
+        .flat_map(|project| std::iter::once(&project.name).chain(&project.aliases))
+        .cloned()
+        .collect();
+    let mut new_projects: Vec<ProjectMetaData> = Vec::new();
+    // Overlapping roots find the same directory several times
+    let mut found_paths: HashSet<String> = HashSet::new();
+    let mut already_registered = 0;
+
+    for root in roots {
+        if !root.is_dir() {
+            eprintln!("Warning: {} is not a directory (ignored)", root.display());
+            continue;
+        }
+
+        for project_root in find_project_roots(&root, max_depth, &markers, &ignore) {
+            let mut project_meta_data = match ProjectMetaData::new(&project_root, None) {
+                Ok(meta) => meta,
+                Err(e) => {
+                    eprintln!("Warning: {:?}", e);
+                    continue;
+                }
+            };
+
+            if project_index
+                .projects
+                .iter()
+                .any(|project| project.path == project_meta_data.path)
+            {
+                already_registered += 1;
+                continue;
+            }
+            if !found_paths.insert(project_meta_data.path.clone()) {
+                continue;
+            }
+
+            project_meta_data.name = unique_name(&project_meta_data.name, &taken_names);
+            taken_names.insert(project_meta_data.name.clone());
+
+            if options.dry_run {
+                println!(
+                    "Would add {} ({})",
+                    project_meta_data.name, project_meta_data.path
+                );
+            }
+            new_projects.push(project_meta_data);
+        }
+    }
+    let added = new_projects.len();
+
+    // The index is written once so its backup still holds the index from before the scan
+    if !options.dry_run && !new_projects.is_empty() {
+        if let Err(e) = project_index.add_projects_and_save(new_projects.clone()) {
+            eprintln!("Error adding the projects to the index: {:?}", e);
+            std::process::exit(1);
+        }
+
+        for project_meta_data in new_projects {
+            println!(
+                "Added {} ({})",
+                project_meta_data.name, project_meta_data.path
+            );
+            let project_config = ProjectConfig::new(project_meta_data);
+            if let Err(e) = project_config.save() {
+                eprintln!(
+                    "Error saving the config of {}, run `doctor --fix` to regenerate it: {:?}",
+                    project_config.meta_data.name, e
+                );
+            }
+        }
+    }
+
+    println!(
+        "{} project(s) {}, {} already registered",
+        added,
+        if options.dry_run { "to add" } else { "added" },
+        already_registered
+    );
+}
+
+/// Directories containing one of `markers`, projects are not searched for nested projects.
+fn find_project_roots(
+    root: &Path,
+    max_depth: usize,
+    markers: &[String],
+    ignore: &[String],
+) -> Vec<PathBuf> {
+    let is_project = |path: &Path| markers.iter().any(|marker| path.join(marker).exists());
+
+    if is_project(root) {
+        return vec![root.to_path_buf()];
+    }
+
+    let mut project_roots = Vec::new();
+    walk_dirs(root, max_depth, ignore, &mut |path| {
+        if is_project(path) {
+            project_roots.push(path.to_path_buf());
+            WalkAction::SkipChildren
+        } else {
+            WalkAction::Continue
+        }
+    });
+    project_roots
+}
+
+/// Appends `-2`, `-3`... to `name` until it is not taken.
+fn unique_name(name: &str, taken_names: &HashSet<String>) -> String {
+    if !taken_names.contains(name) {
+        return name.to_string();
+    }
+
+    (2..)
+        .map(|suffix| format!("{}-{}", name, suffix))
+        .find(|candidate| !taken_names.contains(candidate))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_find_project_roots() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in [
+            "rust_app/.git",
+            "rust_app/nested/.git",
+            "group/node_app",
+            "group/empty",
+            "node_modules/lib",
+        ] {
+            fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        fs::write(root.path().join("group/node_app/package.json"), "{}").unwrap();
+        fs::write(root.path().join("node_modules/lib/package.json"), "{}").unwrap();
+
+        let markers = vec![".git".to_string(), "package.json".to_string()];
+        let ignore = vec!["node_modules".to_string()];
+
+        assert_eq!(
+            find_project_roots(root.path(), 3, &markers, &ignore),
+            vec![
+                root.path().join("group/node_app"),
+                root.path().join("rust_app")
+            ]
+        );
+        assert!(find_project_roots(root.path(), 1, &markers, &ignore)
+            .contains(&root.path().join("rust_app")));
+        assert_eq!(
+            find_project_roots(&root.path().join("rust_app"), 3, &markers, &ignore),
+            vec![root.path().join("rust_app")]
+        );
+    }
+
+    #[test]
+    fn test_unique_name() {
+        let taken_names = HashSet::from(["api".to_string(), "api-2".to_string()]);
+
+        assert_eq!(unique_name("web", &taken_names), "web");
+        assert_eq!(unique_name("api", &taken_names), "api-3");
+    }
+}
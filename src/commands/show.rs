@@ -0,0 +1,54 @@
+use crate::models::{
+    project_config::{project_config_path, ProjectConfig},
+    project_index::ProjectIndex,
+};
+
+pub fn execute(project_name: &str) {
+    let project_index = ProjectIndex::load_or_new();
+
+    let project_meta_data = match project_index.find_project_by_name(project_name) {
+        Ok(meta) => meta,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let project_config = ProjectConfig::load(project_name).unwrap_or_else(|e| {
+        eprintln!("Error loading project config: {:?}", e);
+        std::process::exit(1);
+    });
+
+    let toml_str = toml::Value::try_from(&project_config)
+        .and_then(|value| toml::to_string_pretty(&value))
+        .unwrap_or_else(|e| {
+            eprintln!("Error serializing project config: {:?}", e);
+            std::process::exit(1);
+        });
+
+    println!("Name:        {}", project_meta_data.name);
+    println!("Path:        {}", project_meta_data.path);
+    println!("Created:     {}", project_meta_data.creation_date_utc);
+    if !project_meta_data.types.is_empty() {
+        println!("Type:        {}", project_meta_data.types.join(", "));
+    }
+    if let Some(package) = &project_meta_data.package {
+        let fields = [
+            ("Package:     ", &package.name),
+            ("Version:     ", &package.version),
+            ("Description: ", &package.description),
+        ];
+        for (label, value) in fields {
+            if let Some(value) = value {
+                println!("{}{}", label, value);
+            }
+        }
+    }
+    println!(
+        "Config file: {}",
+        project_config_path(project_name).display()
+    );
+    println!();
+    println!("----- Config -----");
+    print!("{}", toml_str);
+}
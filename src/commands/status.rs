@@ -0,0 +1,209 @@
+use std::{path::Path, thread};
+
+use chrono::{DateTime, Utc};
+use cli_project_manager::lib::{
+    git::{self, RepoStatus},
+    utils,
+};
+
+use crate::models::{project_config::ProjectMetaData, project_index::ProjectIndex};
+
+/// git mostly waits on the disk, so more threads than cores are used
+const MAX_THREADS: usize = 16;
+
+#[derive(Debug)]
+enum ProjectStatus {
+    MissingPath,
+    NotARepository,
+    Repository(RepoStatus),
+}
+
+impl ProjectStatus {
+    fn is_dirty(&self) -> bool {
+        matches!(self, ProjectStatus::Repository(status) if status.is_dirty())
+    }
+}
+
+pub fn execute(dirty_only: bool) {
+    let project_index = ProjectIndex::load_or_new();
+
+    let statuses = collect_statuses(&project_index.projects);
+    let now = Utc::now();
+
+    let rows: Vec<Vec<String>> = project_index
+        .projects
+        .iter()
+        .zip(&statuses)
+        .filter(|(_, status)| !dirty_only || status.is_dirty())
+        .map(|(project, status)| render_row(project, status, now))
+        .collect();
+
+    if rows.is_empty() {
+        if dirty_only {
+            println!("No project with uncommitted changes");
+        } else {
+            println!("No project found");
+        }
+        return;
+    }
+
+    let headers = [
+        "NAME",
+        "BRANCH",
+        "CHANGES",
+        "UPSTREAM",
+        "STASHES",
+        "LAST COMMIT",
+    ];
+    print!("{}", utils::format_table(&headers, &rows));
+}
+
+/// Reads the status of every project concurrently, in the index order
+fn collect_statuses(projects: &[ProjectMetaData]) -> Vec<ProjectStatus> {
+    let threads = thread::available_parallelism()
+        .map(|cores| cores.get() * 2)
+        .unwrap_or(MAX_THREADS)
+        .min(MAX_THREADS);
+    let chunk_size = projects.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = projects
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(project_status).collect::<Vec<_>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Failed to read a project status"))
+            .collect()
+    })
+}
+
+fn project_status(project: &ProjectMetaData) -> ProjectStatus {
+    let path = Path::new(&project.path);
+
+    if !path.is_dir() {
+        return ProjectStatus::MissingPath;
+    }
+
+    match git::status(path) {
+        Some(status) => ProjectStatus::Repository(status),
+        None => ProjectStatus::NotARepository,
+    }
+}
+
+fn render_row(
+    project: &ProjectMetaData,
+    status: &ProjectStatus,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let status = match status {
+        ProjectStatus::MissingPath => {
+            return vec![
+                project.name.clone(),
+                String::new(),
+                "path not found".to_string(),
+            ]
+        }
+        ProjectStatus::NotARepository => {
+            return vec![
+                project.name.clone(),
+                String::new(),
+                "not a git repository".to_string(),
+            ]
+        }
+        ProjectStatus::Repository(status) => status,
+    };
+
+    let changes = match status.changes {
+        0 => "clean".to_string(),
+        count => format!("{} changed", count),
+    };
+    let upstream = match status.ahead_behind {
+        None => "no upstream".to_string(),
+        Some((0, 0)) => "up to date".to_string(),
+        Some((ahead, 0)) => format!("ahead {}", ahead),
+        Some((0, behind)) => format!("behind {}", behind),
+        Some((ahead, behind)) => format!("ahead {}, behind {}", ahead, behind),
+    };
+    let last_commit = status
+        .last_commit
+        .map(|date| utils::format_elapsed(date, now))
+        .unwrap_or_else(|| "never".to_string());
+
+    vec![
+        project.name.clone(),
+        status.branch.clone(),
+        changes,
+        upstream,
+        status.stashes.to_string(),
+        last_commit,
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_row() {
+        let now = Utc::now();
+        let status = ProjectStatus::Repository(RepoStatus {
+            branch: "main".to_string(),
+            changes: 3,
+            ahead_behind: Some((2, 0)),
+            stashes: 1,
+            last_commit: Some(now - chrono::Duration::hours(5)),
+        });
+
+        assert_eq!(
+            render_row(&ProjectMetaData::sample("api"), &status, now),
+            vec!["api", "main", "3 changed", "ahead 2", "1", "5h ago"]
+        );
+        assert!(status.is_dirty());
+
+        let status = ProjectStatus::Repository(RepoStatus {
+            branch: "main".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            render_row(&ProjectMetaData::sample("new"), &status, now),
+            vec!["new", "main", "clean", "no upstream", "0", "never"]
+        );
+        assert!(!status.is_dirty());
+
+        assert_eq!(
+            render_row(
+                &ProjectMetaData::sample("gone"),
+                &ProjectStatus::MissingPath,
+                now
+            ),
+            vec!["gone", "", "path not found"]
+        );
+    }
+
+    #[test]
+    fn test_collect_statuses_keeps_the_index_order() {
+        let existing_dir = tempfile::tempdir().unwrap();
+        let projects: Vec<ProjectMetaData> = (0..40)
+            .map(|i| {
+                let mut project = ProjectMetaData::sample(&format!("project-{}", i));
+                if i % 3 == 0 {
+                    project.path = existing_dir.path().to_string_lossy().to_string();
+                }
+                project
+            })
+            .collect();
+
+        let statuses = collect_statuses(&projects);
+
+        assert_eq!(statuses.len(), projects.len());
+        for (i, status) in statuses.iter().enumerate() {
+            if i % 3 == 0 {
+                assert!(matches!(status, ProjectStatus::NotARepository));
+            } else {
+                assert!(matches!(status, ProjectStatus::MissingPath));
+            }
+        }
+    }
+}
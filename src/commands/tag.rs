@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use clap::Subcommand;
+use clap_complete::ArgValueCandidates;
+
+use super::{
+    completions::project_name_candidates,
+    list_field::{self, ListField},
+};
+
+use crate::models::{project_config::ProjectMetaData, project_index::ProjectIndex};
+
+#[derive(Subcommand, Debug)]
+pub enum TagAction {
+    /// Add tags to a project
+    Add {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from a project
+    Rm {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// List the tags of a project, or every tag with its number of projects
+    Ls {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: Option<String>,
+    },
+}
+
+/// The `tags` of a project
+struct Tags;
+
+impl ListField for Tags {
+    const NAME: &'static str = "tag";
+    const PLURAL: &'static str = "tags";
+    const ADDED: &'static str = "tagged";
+    const REMOVED: &'static str = "untagged";
+
+    fn values(project: &ProjectMetaData) -> &Vec<String> {
+        &project.tags
+    }
+
+    fn values_mut(project: &mut ProjectMetaData) -> &mut Vec<String> {
+        &mut project.tags
+    }
+
+    fn missing(project_name: &str, tag: &str) -> anyhow::Error {
+        anyhow::anyhow!("{} is not tagged {}", project_name, tag)
+    }
+}
+
+pub fn execute(action: &TagAction) {
+    let result = match action {
+        TagAction::Add { project_name, tags } => list_field::add::<Tags>(project_name, tags),
+        TagAction::Rm { project_name, tags } => list_field::remove::<Tags>(project_name, tags),
+        TagAction::Ls {
+            project_name: Some(project_name),
+        } => list_field::list_project::<Tags>(project_name),
+        TagAction::Ls { project_name: None } => {
+            list_all();
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Prints every tag with its number of projects
+fn list_all() {
+    let project_index = ProjectIndex::load_or_new();
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for tag in project_index
+        .projects
+        .iter()
+        .flat_map(|project| &project.tags)
+    {
+        *counts.entry(tag).or_default() += 1;
+    }
+
+    for (tag, count) in counts {
+        println!("{} ({})", tag, count);
+    }
+}
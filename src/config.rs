@@ -6,19 +6,19 @@ use std::sync::RwLock;
 use std::{env, path};
 use std::{fs, io, path::PathBuf};
 
+use crate::models::settings::Settings;
+
 #[derive(Deserialize, Debug)]
 
 pub struct Config {
     pub base_dir: path::PathBuf,
     pub project_index_file: path::PathBuf,
+    pub settings: Settings,
 }
 
 impl Config {
     pub fn new() -> Result<Self, anyhow::Error> {
-        let base_dir = match env::var("PROJECT_MANAGER_CLI_HOME") {
-            Ok(path) => path::PathBuf::from(path),
-            Err(_) => dirs::home_dir().unwrap().join(".project_manager_cli"),
-        };
+        let base_dir = base_dir();
 
         let project_config_dir = base_dir.join("projects");
 
@@ -26,7 +26,7 @@ impl Config {
         ensure_directory_exists(&project_config_dir)
             .context("Failed to ensure project config directory exists")?;
 
-        let project_index_file = base_dir.join("project_index.toml");
+        let project_index_file = project_index_path(&base_dir);
 
         ensure_file_exists(&project_index_file).context("Failed to create project index file")?;
 
@@ -34,13 +34,28 @@ impl Config {
 
         ensure_file_exists(&cli_configuration_file)?;
 
+        let settings = Settings::load_from_path(&cli_configuration_file)?;
+
         Ok(Config {
             base_dir,
             project_index_file,
+            settings,
         })
     }
 }
 
+/// Directory holding the index, the project configs and the settings
+pub fn base_dir() -> path::PathBuf {
+    match env::var("PROJECT_MANAGER_CLI_HOME") {
+        Ok(path) => path::PathBuf::from(path),
+        Err(_) => dirs::home_dir().unwrap().join(".project_manager_cli"),
+    }
+}
+
+pub fn project_index_path(base_dir: &path::Path) -> path::PathBuf {
+    base_dir.join("project_index.toml")
+}
+
 static CONFIG: OnceCell<RwLock<Config>> = OnceCell::new();
 
 // Function to initialize the global config
@@ -66,9 +81,10 @@ fn ensure_directory_exists(dir: &PathBuf) -> io::Result<()> {
 }
 
 /// Ensures that a file exists, creating it if necessary.
+/// An existing file is never truncated, another process may have just written it.
 fn ensure_file_exists(file: &PathBuf) -> io::Result<()> {
     if !file.exists() {
-        File::create(file)?;
+        File::options().create(true).append(true).open(file)?;
     }
     Ok(())
 }
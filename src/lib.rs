@@ -1,4 +1,10 @@
 pub mod lib {
+    pub mod atomic_write;
+    pub mod detection;
+    pub mod environment;
+    pub mod file_lock;
     pub mod fuzzing_matching;
+    pub mod git;
     pub mod utils;
+    pub mod walk;
 }
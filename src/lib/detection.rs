@@ -0,0 +1,229 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Name, version and description read from a project manifest
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct PackageInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl PackageInfo {
+    fn is_empty(&self) -> bool {
+        *self == PackageInfo::default()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Detection {
+    /// Languages and toolchains, in lowercase (`rust`, `node`, `gradle`...)
+    pub types: Vec<String>,
+    /// Read from the first manifest providing one
+    pub package: Option<PackageInfo>,
+}
+
+type ManifestReader = fn(&str) -> Option<PackageInfo>;
+
+/// Manifest file, project types it reveals and how to read its package info
+const MANIFESTS: &[(&str, &[&str], Option<ManifestReader>)] = &[
+    ("Cargo.toml", &["rust"], Some(read_cargo_toml)),
+    ("package.json", &["node"], Some(read_package_json)),
+    ("tsconfig.json", &["typescript"], None),
+    ("deno.json", &["deno"], None),
+    ("pyproject.toml", &["python"], Some(read_pyproject_toml)),
+    ("setup.py", &["python"], None),
+    ("requirements.txt", &["python"], None),
+    ("go.mod", &["go"], Some(read_go_mod)),
+    ("pom.xml", &["java", "maven"], None),
+    ("build.gradle", &["java", "gradle"], None),
+    ("build.gradle.kts", &["kotlin", "gradle"], None),
+    ("Gemfile", &["ruby"], None),
+    ("composer.json", &["php"], Some(read_package_json)),
+    ("mix.exs", &["elixir"], None),
+    ("CMakeLists.txt", &["c/c++", "cmake"], None),
+];
+
+/// Detects the project types from the manifest files at the root of `path`.
+/// Unreadable or invalid manifests still reveal the type, only the package info is skipped.
+pub fn detect(path: &Path) -> Detection {
+    let mut detection = Detection::default();
+
+    for (file_name, types, reader) in MANIFESTS {
+        let manifest_path = path.join(file_name);
+        if !manifest_path.is_file() {
+            continue;
+        }
+
+        for project_type in types.iter() {
+            if !detection.types.iter().any(|t| t == project_type) {
+                detection.types.push(project_type.to_string());
+            }
+        }
+
+        if let (None, Some(reader)) = (&detection.package, reader) {
+            detection.package = fs::read_to_string(&manifest_path)
+                .ok()
+                .and_then(|content| reader(&content))
+                .filter(|package| !package.is_empty());
+        }
+    }
+
+    detection
+}
+
+fn string_field(value: Option<&toml::Value>) -> Option<String> {
+    value.and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn read_toml_table(table: Option<&toml::Value>) -> Option<PackageInfo> {
+    let table = table?;
+
+    Some(PackageInfo {
+        name: string_field(table.get("name")),
+        // `version.workspace = true` is not a string and is ignored
+        version: string_field(table.get("version")),
+        description: string_field(table.get("description")),
+    })
+}
+
+fn read_cargo_toml(content: &str) -> Option<PackageInfo> {
+    let manifest: toml::Value = toml::from_str(content).ok()?;
+    read_toml_table(manifest.get("package"))
+}
+
+fn read_pyproject_toml(content: &str) -> Option<PackageInfo> {
+    let manifest: toml::Value = toml::from_str(content).ok()?;
+
+    read_toml_table(manifest.get("project"))
+        .or_else(|| read_toml_table(manifest.get("tool").and_then(|tool| tool.get("poetry"))))
+}
+
+fn read_package_json(content: &str) -> Option<PackageInfo> {
+    let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+    let field = |key: &str| {
+        manifest
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
+    Some(PackageInfo {
+        name: field("name"),
+        version: field("version"),
+        description: field("description"),
+    })
+}
+
+fn read_go_mod(content: &str) -> Option<PackageInfo> {
+    let module = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?;
+
+    Some(PackageInfo {
+        name: Some(module.trim().to_string()),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_rust_project() {
+        let project_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            project_dir.path().join("Cargo.toml"),
+            r#"
+                [package]
+                name = "my_crate"
+                version = "0.3.1"
+                description = "Does things"
+
+                [dependencies]
+                serde = "1"
+            "#,
+        )
+        .unwrap();
+
+        let detection = detect(project_dir.path());
+
+        assert_eq!(detection.types, vec!["rust"]);
+        assert_eq!(
+            detection.package,
+            Some(PackageInfo {
+                name: Some("my_crate".to_string()),
+                version: Some("0.3.1".to_string()),
+                description: Some("Does things".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_detect_several_types() {
+        let project_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            project_dir.path().join("package.json"),
+            r#"{ "name": "web", "version": "1.0.0" }"#,
+        )
+        .unwrap();
+        fs::write(project_dir.path().join("tsconfig.json"), "{}").unwrap();
+        fs::write(project_dir.path().join("build.gradle"), "").unwrap();
+        fs::write(project_dir.path().join("build.gradle.kts"), "").unwrap();
+
+        let detection = detect(project_dir.path());
+
+        assert_eq!(
+            detection.types,
+            vec!["node", "typescript", "java", "gradle", "kotlin"]
+        );
+        assert_eq!(detection.package.unwrap().name, Some("web".to_string()));
+    }
+
+    #[test]
+    fn test_detect_invalid_manifest() {
+        let project_dir = tempfile::tempdir().unwrap();
+        fs::write(project_dir.path().join("Cargo.toml"), "[package").unwrap();
+        fs::write(
+            project_dir.path().join("go.mod"),
+            "module github.com/user/tool\n\ngo 1.22\n",
+        )
+        .unwrap();
+
+        let detection = detect(project_dir.path());
+
+        assert_eq!(detection.types, vec!["rust", "go"]);
+        assert_eq!(
+            detection.package.unwrap().name,
+            Some("github.com/user/tool".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_pyproject_toml() {
+        let poetry = r#"
+            [tool.poetry]
+            name = "api"
+            version = "2.0.0"
+        "#;
+
+        let package = read_pyproject_toml(poetry).unwrap();
+
+        assert_eq!(package.name, Some("api".to_string()));
+        assert_eq!(package.version, Some("2.0.0".to_string()));
+        assert_eq!(package.description, None);
+    }
+
+    #[test]
+    fn test_detect_empty_directory() {
+        let project_dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(detect(project_dir.path()), Detection::default());
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::Context;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+static VARIABLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}").expect("Failed to create regex exp")
+});
+
+static NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").expect("Failed to create regex exp"));
+
+/// Variable names are written unquoted in `export KEY=value` lines, so only shell identifiers are allowed
+pub fn is_valid_name(name: &str) -> bool {
+    NAME_REGEX.is_match(name)
+}
+
+/// Parses the content of a dotenv file into ordered `(key, value)` pairs.
+///
+/// Supported syntax: `KEY=value`, `export KEY=value`, `# comments`,
+/// single quoted values (taken literally) and double quoted values (`\n`, `\"` and `\\` escapes).
+/// Values that are not single quoted are interpolated with `lookup`, see [`interpolate`].
+pub fn parse_dotenv<F>(content: &str, lookup: F) -> Result<Vec<(String, String)>, anyhow::Error>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut variables: Vec<(String, String)> = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, raw_value) = line
+            .split_once('=')
+            .with_context(|| format!("Line {}: expected KEY=value", line_number + 1))?;
+        let key = key.trim();
+
+        if !is_valid_name(key) {
+            return Err(anyhow::anyhow!(
+                "Line {}: invalid variable name `{}`",
+                line_number + 1,
+                key
+            ));
+        }
+
+        let raw_value = raw_value.trim();
+        let value = if let Some(literal) = strip_quotes(raw_value, '\'') {
+            literal.to_string()
+        } else {
+            let value = match strip_quotes(raw_value, '"') {
+                Some(quoted) => unescape(quoted),
+                None => strip_inline_comment(raw_value).to_string(),
+            };
+
+            // Variables defined earlier in the same file take precedence over `lookup`
+            interpolate(&value, |name| {
+                variables
+                    .iter()
+                    .rev()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.clone())
+                    .or_else(|| lookup(name))
+            })
+        };
+
+        variables.push((key.to_string(), value));
+    }
+
+    Ok(variables)
+}
+
+/// Replaces `${VAR}` and `${VAR:-default}` with the value returned by `lookup`.
+/// Unknown variables without a default are replaced by an empty string.
+pub fn interpolate<F>(value: &str, lookup: F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    VARIABLE_REGEX
+        .replace_all(value, |caps: &Captures| {
+            lookup(&caps[1])
+                .filter(|value| !value.is_empty())
+                .or_else(|| caps.get(2).map(|default| default.as_str().to_string()))
+                .unwrap_or_default()
+        })
+        .to_string()
+}
+
+fn strip_quotes(value: &str, quote: char) -> Option<&str> {
+    if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+        Some(&value[1..value.len() - 1])
+    } else {
+        None
+    }
+}
+
+fn strip_inline_comment(value: &str) -> &str {
+    match value.find(" #") {
+        Some(index) => value[..index].trim_end(),
+        None => value,
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn no_lookup(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let variables = HashMap::from([
+            ("HOME".to_string(), "/home/user".to_string()),
+            ("EMPTY".to_string(), String::new()),
+        ]);
+        let lookup = |name: &str| variables.get(name).cloned();
+
+        let samples = vec![
+            ("${HOME}/bin", "/home/user/bin"),
+            ("no variable", "no variable"),
+            ("${UNKNOWN}", ""),
+            ("${UNKNOWN:-fallback}", "fallback"),
+            ("${EMPTY:-fallback}", "fallback"),
+            ("${HOME:-fallback}", "/home/user"),
+            ("$HOME", "$HOME"),
+            ("${HOME}${HOME}", "/home/user/home/user"),
+        ];
+
+        for (input, expected) in samples {
+            assert_eq!(interpolate(input, lookup), expected, "Failed on {}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let content = r#"
+            # Database
+            DB_HOST=localhost
+            export DB_PORT=5432
+            DB_URL="postgres://${DB_HOST}:${DB_PORT}/app"
+            LITERAL='${DB_HOST} stays'
+            MESSAGE="line1\nline2 \"quoted\""
+            COMMENTED=value # comment
+            EMPTY=
+        "#;
+
+        let variables = parse_dotenv(content, no_lookup).unwrap();
+
+        assert_eq!(
+            variables,
+            vec![
+                ("DB_HOST".to_string(), "localhost".to_string()),
+                ("DB_PORT".to_string(), "5432".to_string()),
+                (
+                    "DB_URL".to_string(),
+                    "postgres://localhost:5432/app".to_string()
+                ),
+                ("LITERAL".to_string(), "${DB_HOST} stays".to_string()),
+                ("MESSAGE".to_string(), "line1\nline2 \"quoted\"".to_string()),
+                ("COMMENTED".to_string(), "value".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_uses_lookup() {
+        let lookup = |name: &str| (name == "PROJECT_PATH").then(|| "/srv/app".to_string());
+
+        let variables = parse_dotenv("DATA_DIR=${PROJECT_PATH}/data", lookup).unwrap();
+
+        assert_eq!(
+            variables,
+            vec![("DATA_DIR".to_string(), "/srv/app/data".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv_invalid_lines() {
+        assert!(parse_dotenv("NOT A VARIABLE", no_lookup).is_err());
+        assert!(parse_dotenv("MY KEY=value", no_lookup).is_err());
+        assert!(parse_dotenv("=value", no_lookup).is_err());
+        assert!(parse_dotenv("X;echo INJECTED;Y=value", no_lookup).is_err());
+        assert!(parse_dotenv("1KEY=value", no_lookup).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("DB_HOST"));
+        assert!(is_valid_name("_private1"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("1KEY"));
+        assert!(!is_valid_name("MY-KEY"));
+        assert!(!is_valid_name("X;echo INJECTED;Y"));
+        assert!(!is_valid_name("$(reboot)"));
+    }
+}
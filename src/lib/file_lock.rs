@@ -0,0 +1,88 @@
+use std::{
+    fs::{File, TryLockError},
+    io,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Exclusive advisory lock on a file, released when dropped or when the process exits.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Waits at most `timeout` for the lock, the file is created if needed.
+    /// Fails with `io::ErrorKind::TimedOut` when another process keeps holding it.
+    pub fn acquire(path: &Path, timeout: Duration) -> io::Result<FileLock> {
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        let start = Instant::now();
+
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(FileLock { file }),
+                Err(TryLockError::WouldBlock) if start.elapsed() < timeout => {
+                    thread::sleep(RETRY_INTERVAL)
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!(
+                            "{} is still locked after {}s",
+                            path.display(),
+                            timeout.as_secs_f32()
+                        ),
+                    ))
+                }
+                Err(TryLockError::Error(e)) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.lock");
+
+        let lock = FileLock::acquire(&path, Duration::ZERO).unwrap();
+
+        let error = FileLock::acquire(&path, Duration::from_millis(50)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+        drop(lock);
+        assert!(FileLock::acquire(&path, Duration::ZERO).is_ok());
+    }
+
+    #[test]
+    fn test_lock_waits_for_the_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.lock");
+        let lock = FileLock::acquire(&path, Duration::ZERO).unwrap();
+
+        let holder = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            drop(lock);
+        });
+
+        assert!(FileLock::acquire(&path, Duration::from_secs(5)).is_ok());
+        holder.join().unwrap();
+    }
+}
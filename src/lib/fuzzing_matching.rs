@@ -1,11 +1,34 @@
-use std::{
-    cmp::{max, min},
-    collections::HashMap,
-};
+use std::{cmp::min, collections::HashMap};
 
 use crate::lib::utils;
 
+const SCORE_MATCH: i32 = 16;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_GAP_EXTENSION: i32 = 1;
+/// Matching the first character of a word (start, after `-`, `_`, `/`, space...)
+const BONUS_BOUNDARY: i32 = 8;
+/// Matching an uppercase letter after a lowercase one, or a digit after a letter
+const BONUS_CAMEL_CASE: i32 = 7;
+const BONUS_CONSECUTIVE: i32 = 4;
+/// The bonus of the first pattern character counts more, it is the one we type most carefully
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+/// Share of the score (out of 100) given by how much of the candidate the pattern covers
+const COVERAGE_WEIGHT: f64 = 15.0;
+/// Best score of a candidate that doesn't contain the pattern as a subsequence (typos)
+const FALLBACK_MAX_SCORE: f64 = 60.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub text: String,
+    /// From 0 to 100
+    pub score: u32,
+    /// Indices of the matched characters (not bytes) of `text`, empty for typo matches
+    pub positions: Vec<usize>,
+}
+
 fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
     let length_a = a.len();
     let length_b = b.len();
 
@@ -15,17 +38,13 @@ fn levenshtein_distance(a: &str, b: &str) -> usize {
         distance[0] = i;
     }
 
-    for j in 0..=length_b {
-        distances[0][j] = j;
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
     }
 
     for i in 1..=length_a {
         for j in 1..=length_b {
-            let cost = if a.as_bytes()[i - 1] == b.as_bytes()[j - 1] {
-                0
-            } else {
-                1
-            };
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
 
             distances[i][j] = min(
                 distances[i][j - 1] + 1,
@@ -37,42 +56,140 @@ fn levenshtein_distance(a: &str, b: &str) -> usize {
     distances[length_a][length_b]
 }
 
-pub fn compute_matching_score(s1: &str, s2: &str) -> f64 {
-    let s1 = utils::normalize_string(s1);
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Bonus for matching the character at `index`, depending on the character before it
+fn position_bonus(chars: &[char], index: usize) -> i32 {
+    let current = chars[index];
+    let Some(&previous) = index.checked_sub(1).and_then(|i| chars.get(i)) else {
+        return BONUS_BOUNDARY;
+    };
+
+    if !previous.is_alphanumeric() && current.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if (previous.is_lowercase() && current.is_uppercase())
+        || (previous.is_alphabetic() && current.is_numeric())
+    {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
+
+/// Scores `pattern` as a case insensitive subsequence of `candidate`, fzf style.
+/// Returns the best raw score with the positions of the matched characters.
+fn subsequence_match(candidate: &[char], pattern: &[char]) -> Option<(i32, Vec<usize>)> {
+    let (n, m) = (candidate.len(), pattern.len());
+    if m == 0 || m > n {
+        return None;
+    }
+
+    let folded: Vec<char> = candidate.iter().map(|c| fold_case(*c)).collect();
+    let bonuses: Vec<i32> = (0..n).map(|j| position_bonus(candidate, j)).collect();
+
+    // scores[i][j]: best score with the i-th pattern character matched at j, previous[i][j]: where the (i-1)-th one matched
+    let mut scores: Vec<Vec<Option<i32>>> = vec![vec![None; n]; m];
+    let mut previous: Vec<Vec<usize>> = vec![vec![0; n]; m];
 
-    let distance = levenshtein_distance(&s1, s2);
+    for (i, pattern_char) in pattern.iter().map(|c| fold_case(*c)).enumerate() {
+        for j in i..n {
+            if folded[j] != pattern_char {
+                continue;
+            }
 
-    let (len1, len2) = (s1.len(), s2.len());
+            if i == 0 {
+                scores[0][j] = Some(SCORE_MATCH + bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER);
+                continue;
+            }
 
-    let mut score = 1.0 - (distance as f64 / max(len1, len2) as f64);
+            let best = (i - 1..j)
+                .filter_map(|k| {
+                    let score = scores[i - 1][k]?;
+                    let transition = if k + 1 == j {
+                        bonuses[j].max(BONUS_CONSECUTIVE)
+                    } else {
+                        let gap = (j - k - 1) as i32;
+                        bonuses[j] - PENALTY_GAP_START - PENALTY_GAP_EXTENSION * (gap - 1)
+                    };
+                    Some((score + transition, k))
+                })
+                // Prefer the latest previous match on equal scores, it keeps matches tight
+                .max_by_key(|(score, k)| (*score, *k));
 
-    if s1.starts_with(s2) {
-        score += 0.3;
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(SCORE_MATCH + score);
+                previous[i][j] = k;
+            }
+        }
     }
 
-    if distance <= 2 {
-        score += 0.1
+    let (best_score, mut j) = (0..n)
+        .filter_map(|j| scores[m - 1][j].map(|score| (score, j)))
+        .max_by_key(|(score, j)| (*score, std::cmp::Reverse(*j)))?;
+
+    let mut positions = vec![j; m];
+    for i in (1..m).rev() {
+        j = previous[i][j];
+        positions[i - 1] = j;
     }
 
-    if s1.contains(s2) {
-        score += 0.1
+    Some((best_score, positions))
+}
+
+/// Scores `candidate` against `pattern` from 0 to 100.
+///
+/// Subsequence matches get bonuses for word boundaries, camelCase and consecutive characters,
+/// so abbreviations like `cpm` find `cli_project_manager`. Candidates not containing the
+/// pattern fall back to a Levenshtein similarity capped below good subsequence matches.
+pub fn fuzzy_match(candidate: &str, pattern: &str) -> FuzzyMatch {
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let pattern_chars: Vec<char> = pattern.chars().collect();
+
+    if let Some((raw_score, positions)) = subsequence_match(&candidate_chars, &pattern_chars) {
+        let m = pattern_chars.len() as i32;
+        let ideal = SCORE_MATCH * m
+            + BONUS_BOUNDARY * BONUS_FIRST_CHAR_MULTIPLIER
+            + BONUS_CONSECUTIVE * (m - 1);
+        let quality = (raw_score.max(0) as f64 / ideal as f64).min(1.0);
+        let coverage = pattern_chars.len() as f64 / candidate_chars.len() as f64;
+        let score = quality * (100.0 - COVERAGE_WEIGHT) + coverage * COVERAGE_WEIGHT;
+
+        return FuzzyMatch {
+            text: candidate.to_string(),
+            score: score.round() as u32,
+            positions,
+        };
     }
 
-    score.clamp(0.0, 1.0)
+    let normalized_candidate = utils::normalize_string(candidate).to_lowercase();
+    let normalized_pattern = utils::normalize_string(pattern).to_lowercase();
+    let distance = levenshtein_distance(&normalized_candidate, &normalized_pattern);
+    let length = normalized_candidate
+        .chars()
+        .count()
+        .max(normalized_pattern.chars().count())
+        .max(1);
+    let similarity = 1.0 - distance as f64 / length as f64;
+
+    FuzzyMatch {
+        text: candidate.to_string(),
+        score: (similarity * FALLBACK_MAX_SCORE).round() as u32,
+        positions: Vec::new(),
+    }
 }
 
-pub fn matching(source: Vec<&str>, pattern: &str) -> HashMap<u32, Vec<String>> {
-    let mut result = HashMap::new();
+/// Scores every sample against `pattern`, grouped by score
+pub fn matching(source: Vec<&str>, pattern: &str) -> HashMap<u32, Vec<FuzzyMatch>> {
+    let mut result: HashMap<u32, Vec<FuzzyMatch>> = HashMap::new();
     for sample in source {
-        let score = compute_matching_score(sample, pattern);
-
-        // TODO: Refactor this
-        let score = (score * 100.00).round() as u32;
+        let fuzzy_match = fuzzy_match(sample, pattern);
 
         result
-            .entry(score)
-            .or_insert_with(Vec::new)
-            .push(sample.to_string());
+            .entry(fuzzy_match.score)
+            .or_default()
+            .push(fuzzy_match);
     }
     result
 }
@@ -137,4 +254,56 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_levenshtein_distance_unicode() {
+        assert_eq!(levenshtein_distance("café", "cafe"), 1);
+        assert_eq!(levenshtein_distance("日本語", "日本"), 1);
+    }
+
+    #[test]
+    fn test_fuzzy_match_positions() {
+        let result = fuzzy_match("cli_project_manager", "cpm");
+        assert_eq!(result.positions, vec![0, 4, 12]);
+
+        let result = fuzzy_match("MyBackendService", "mbs");
+        assert_eq!(result.positions, vec![0, 2, 9]);
+
+        let result = fuzzy_match("Überprojekt", "üb");
+        assert_eq!(result.positions, vec![0, 1]);
+
+        assert!(fuzzy_match("frontend", "xyz").positions.is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_match_scores() {
+        let score = |candidate: &str, pattern: &str| fuzzy_match(candidate, pattern).score;
+
+        assert_eq!(score("backend", "backend"), 100);
+        assert_eq!(score("Backend", "backend"), 100);
+
+        // Abbreviations on word boundaries beat scattered letters
+        assert!(score("cli_project_manager", "cpm") > score("compiler", "cpm"));
+        // camelCase humps count as word boundaries
+        assert!(score("MyBackendService", "mbs") > score("mobs-archive", "mbs"));
+        // Consecutive characters beat the same characters spread out
+        assert!(score("legacy-api", "api") > score("legacy-apps-index", "api"));
+        // Tighter candidates win
+        assert!(score("web", "web") > score("web-legacy", "web"));
+        // Typos still match, below real subsequence matches
+        assert!(score("cli_project_manager", "cli_projetc") > 0);
+        assert!(score("cli_project_manager", "cli_projetc") < score("cli_project_manager", "cpm"));
+    }
+
+    #[test]
+    fn test_matching_groups_by_score() {
+        let result = matching(vec!["api", "web", "api-v2"], "api");
+
+        assert_eq!(result[&100].len(), 1);
+        assert_eq!(result[&100][0].text, "api");
+        assert_eq!(
+            result.values().map(|matches| matches.len()).sum::<usize>(),
+            3
+        );
+    }
 }
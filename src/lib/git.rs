@@ -0,0 +1,357 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use chrono::{DateTime, Utc};
+
+/// Finds the git directory of a work tree, following the `gitdir:` link of worktrees and submodules.
+pub fn git_dir(project_path: &Path) -> Option<PathBuf> {
+    let dot_git = project_path.join(".git");
+
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let git_dir = content.trim().strip_prefix("gitdir:")?.trim();
+
+    Some(project_path.join(git_dir))
+}
+
+/// Name of the checked out branch, or the abbreviated commit hash when the HEAD is detached.
+/// Only the local `.git` data is read.
+pub fn current_branch(project_path: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir(project_path)?.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    match head.strip_prefix("ref: ") {
+        Some(reference) => Some(
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_string(),
+        ),
+        None => head.get(..7).map(|hash| hash.to_string()),
+    }
+}
+
+/// State of a work tree, read from the local `.git` data only: the remotes are never fetched.
+#[derive(Debug, Default, PartialEq)]
+pub struct RepoStatus {
+    /// Checked out branch, or the abbreviated commit hash when the HEAD is detached
+    pub branch: String,
+    /// Number of modified, staged and untracked files
+    pub changes: usize,
+    /// Commits ahead and behind the upstream branch, `None` without upstream
+    pub ahead_behind: Option<(u32, u32)>,
+    pub stashes: usize,
+    /// `None` until the first commit
+    pub last_commit: Option<DateTime<Utc>>,
+}
+
+impl RepoStatus {
+    pub fn is_dirty(&self) -> bool {
+        self.changes > 0
+    }
+}
+
+/// Reads the status of the work tree with the git CLI, `None` when it isn't a git repository.
+pub fn status(project_path: &Path) -> Option<RepoStatus> {
+    git_dir(project_path)?;
+
+    let porcelain = run_git(project_path, &["status", "--porcelain=v2", "--branch"])?;
+    let mut status = parse_porcelain_v2(&porcelain);
+
+    status.stashes = run_git(project_path, &["stash", "list"])
+        .map(|stashes| stashes.lines().count())
+        .unwrap_or_default();
+    status.last_commit = run_git(project_path, &["log", "-1", "--format=%cI"]).and_then(|date| {
+        DateTime::parse_from_rfc3339(date.trim())
+            .map(|date| date.with_timezone(&Utc))
+            .ok()
+    });
+
+    Some(status)
+}
+
+/// Runs a git command in the work tree and returns its output, `None` when it fails.
+fn run_git(project_path: &Path, args: &[&str]) -> Option<String> {
+    // Optional locks would make concurrent runs and the user's own git commands fail
+    let output = Command::new("git")
+        .arg("--no-optional-locks")
+        .args(args)
+        .current_dir(project_path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Parses the output of `git status --porcelain=v2 --branch`.
+fn parse_porcelain_v2(porcelain: &str) -> RepoStatus {
+    let mut status = RepoStatus::default();
+    let mut oid = "";
+
+    for line in porcelain.lines() {
+        let Some(header) = line.strip_prefix("# ") else {
+            // Every other line is a changed, unmerged or untracked file
+            status.changes += 1;
+            continue;
+        };
+
+        if let Some(value) = header.strip_prefix("branch.oid ") {
+            oid = value;
+        } else if let Some(value) = header.strip_prefix("branch.head ") {
+            status.branch = value.to_string();
+        } else if let Some(value) = header.strip_prefix("branch.ab ") {
+            let mut counts = value
+                .split_whitespace()
+                .map(|count| count.trim_start_matches(['+', '-']).parse().unwrap_or(0));
+            status.ahead_behind = Some((
+                counts.next().unwrap_or_default(),
+                counts.next().unwrap_or_default(),
+            ));
+        }
+    }
+
+    if status.branch == "(detached)" {
+        status.branch = oid.get(..7).unwrap_or(oid).to_string();
+    }
+
+    status
+}
+
+/// Location of a repository deduced from its URL, used to lay out the clones as `<host>/<owner>/<repo>`.
+#[derive(Debug, PartialEq)]
+pub struct RepositoryUrl {
+    /// `local` for `file://` URLs and paths
+    pub host: String,
+    /// Empty when the repository sits at the root of the host, may contain `/` for nested groups
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepositoryUrl {
+    /// Parses `scheme://[user@]host[:port]/path`, scp-like `[user@]host:path`, `file://` URLs and local paths.
+    /// `.` and `..` segments are dropped from the owner so the workspace path stays under the root.
+    pub fn parse(url: &str) -> Option<RepositoryUrl> {
+        let url = url.trim().trim_end_matches('/');
+
+        let (host, path) = if let Some(path) = url.strip_prefix("file://") {
+            (None, path)
+        } else if let Some((_, rest)) = url.split_once("://") {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            let host = host.split(':').next()?;
+            (Some(host), path)
+        } else if let Some((authority, path)) = url
+            .split_once(':')
+            .filter(|(authority, _)| !authority.is_empty() && !authority.contains('/'))
+        {
+            (authority.rsplit('@').next(), path)
+        } else {
+            (None, url)
+        };
+
+        let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let repo = segments.pop()?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        if repo.is_empty() || repo == "." || repo == ".." {
+            return None;
+        }
+
+        if host.is_some_and(|host| host == "." || host == "..") {
+            return None;
+        }
+        segments.retain(|segment| *segment != "." && *segment != "..");
+
+        let owner = match host {
+            Some(_) => segments.join("/"),
+            // Only the parent directory of a local repository is kept
+            None => segments.last().map(|s| s.to_string()).unwrap_or_default(),
+        };
+
+        Some(RepositoryUrl {
+            host: host
+                .filter(|h| !h.is_empty())
+                .unwrap_or("local")
+                .to_string(),
+            owner,
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Where the repository is cloned under the workspace root
+    pub fn workspace_path(&self, root: &Path) -> PathBuf {
+        let mut path = root.join(&self.host);
+        if !self.owner.is_empty() {
+            path.push(&self.owner);
+        }
+        path.join(&self.repo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_branch() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let git_dir = project_dir.path().join(".git");
+        fs::create_dir(&git_dir).unwrap();
+
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/feature/login\n").unwrap();
+        assert_eq!(
+            current_branch(project_dir.path()),
+            Some("feature/login".to_string())
+        );
+
+        fs::write(
+            git_dir.join("HEAD"),
+            "9fceb02d0ae598e95dc970b74767f19372d61af8\n",
+        )
+        .unwrap();
+        assert_eq!(
+            current_branch(project_dir.path()),
+            Some("9fceb02".to_string())
+        );
+    }
+
+    #[test]
+    fn test_current_branch_of_worktree() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let worktree_git_dir = project_dir.path().join("main/.git/worktrees/wt");
+        fs::create_dir_all(&worktree_git_dir).unwrap();
+        fs::create_dir(project_dir.path().join("wt")).unwrap();
+
+        fs::write(worktree_git_dir.join("HEAD"), "ref: refs/heads/hotfix\n").unwrap();
+        fs::write(
+            project_dir.path().join("wt/.git"),
+            "gitdir: ../main/.git/worktrees/wt\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            current_branch(&project_dir.path().join("wt")),
+            Some("hotfix".to_string())
+        );
+    }
+
+    #[test]
+    fn test_current_branch_without_git() {
+        let project_dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(current_branch(project_dir.path()), None);
+    }
+
+    #[test]
+    fn test_parse_porcelain_v2() {
+        let porcelain = "\
+# branch.oid 9fceb02d0ae598e95dc970b74767f19372d61af8
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+1 .M N... 100644 100644 100644 3b18e512 3b18e512 src/main.rs
+? notes.txt
+";
+
+        assert_eq!(
+            parse_porcelain_v2(porcelain),
+            RepoStatus {
+                branch: "main".to_string(),
+                changes: 2,
+                ahead_behind: Some((2, 1)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_porcelain_v2_detached_without_upstream() {
+        let porcelain = "\
+# branch.oid 9fceb02d0ae598e95dc970b74767f19372d61af8
+# branch.head (detached)
+";
+
+        let status = parse_porcelain_v2(porcelain);
+        assert_eq!(status.branch, "9fceb02");
+        assert_eq!(status.ahead_behind, None);
+        assert!(!status.is_dirty());
+    }
+
+    #[test]
+    fn test_parse_repository_url() {
+        let samples = [
+            (
+                "https://github.com/rust-lang/cargo.git",
+                "github.com",
+                "rust-lang",
+                "cargo",
+            ),
+            (
+                "https://user@gitlab.com/group/sub/tool/",
+                "gitlab.com",
+                "group/sub",
+                "tool",
+            ),
+            (
+                "ssh://git@example.com:2222/team/api.git",
+                "example.com",
+                "team",
+                "api",
+            ),
+            (
+                "git@github.com:owner/repo.git",
+                "github.com",
+                "owner",
+                "repo",
+            ),
+            ("file:///srv/git/team/widget.git", "local", "team", "widget"),
+            ("/srv/git/widget.git", "local", "git", "widget"),
+            ("widget", "local", "", "widget"),
+            ("host:../shared/repo.git", "host", "shared", "repo"),
+            ("https://host/../../repo", "host", "", "repo"),
+            ("https://host/team/./../repo", "host", "team", "repo"),
+            ("../../repo", "local", "", "repo"),
+        ];
+
+        for (url, host, owner, repo) in samples {
+            assert_eq!(
+                RepositoryUrl::parse(url),
+                Some(RepositoryUrl {
+                    host: host.to_string(),
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                }),
+                "Failed on {}",
+                url
+            );
+        }
+
+        assert_eq!(RepositoryUrl::parse("https://github.com"), None);
+        assert_eq!(RepositoryUrl::parse(""), None);
+        assert_eq!(RepositoryUrl::parse("..:repo"), None);
+        assert_eq!(RepositoryUrl::parse("https://host/team/.."), None);
+    }
+
+    #[test]
+    fn test_workspace_path() {
+        let url = RepositoryUrl::parse("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(
+            url.workspace_path(Path::new("/home/me/code")),
+            PathBuf::from("/home/me/code/github.com/owner/repo")
+        );
+
+        let url = RepositoryUrl::parse("widget").unwrap();
+        assert_eq!(
+            url.workspace_path(Path::new("/code")),
+            PathBuf::from("/code/local/widget")
+        );
+    }
+}
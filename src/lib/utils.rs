@@ -1,5 +1,6 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{collections::HashMap, hash::Hash, path::PathBuf};
 
+use chrono::{DateTime, NaiveDateTime, Utc};
 use regex::Regex;
 
 pub fn normalize_string(input: &str) -> String {
@@ -14,16 +15,101 @@ where
     // Convert the HashMap into a vector of tuples (key, value)
     let mut sorted: Vec<(K, &V)> = map.iter().map(|(k, v)| (*k, v)).collect();
 
-    sorted.sort_by(|a, b| b.0.cmp(&a.0));
+    sorted.sort_by_key(|entry| std::cmp::Reverse(entry.0));
 
     sorted
 }
 
+/// Replaces a leading `~` with the home directory.
+pub fn expand_tilde(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Parses the dates written by the CLI (`Utc::now().to_string()`) as well as RFC 3339 dates.
+pub fn parse_utc_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S%.f UTC")
+                .map(|date| date.and_utc())
+                .ok()
+        })
+}
+
+/// Short human readable duration between `date` and `now`, e.g. `3h ago`.
+pub fn format_elapsed(date: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(date);
+
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else if elapsed.num_days() < 30 {
+        format!("{}d ago", elapsed.num_days())
+    } else {
+        date.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Aligns `rows` in columns under `headers`, columns are separated by two spaces.
+pub fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", line.join("  ").trim_end())
+    };
+
+    let mut table = format_row(headers.to_vec());
+    for row in rows {
+        table.push_str(&format_row(row.iter().map(|cell| cell.as_str()).collect()));
+    }
+    table
+}
+
+/// Quotes a string so it can be safely evaluated by a POSIX shell.
+pub fn shell_quote(input: &str) -> String {
+    format!("'{}'", input.replace('\'', "'\\''"))
+}
+
+/// Like `shell_quote` but leaves the strings which need no quoting as they are.
+pub fn shell_word(input: &str) -> String {
+    let is_plain = |c: char| c.is_ascii_alphanumeric() || "@%+=:,./_-".contains(c);
+
+    if !input.is_empty() && input.chars().all(is_plain) {
+        input.to_string()
+    } else {
+        shell_quote(input)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, path::PathBuf};
+
+    use chrono::{Duration, TimeZone, Utc};
 
-    use crate::lib::utils::{normalize_string, sort_hashmap_by_keys};
+    use crate::lib::utils::{
+        expand_tilde, format_elapsed, format_table, normalize_string, parse_utc_date, shell_quote,
+        shell_word, sort_hashmap_by_keys,
+    };
 
     #[test]
     fn test_normalize_string() {
@@ -63,4 +149,79 @@ mod test {
 
         assert_eq!(sort_hashmap_by_keys(&sample), exepected);
     }
+
+    #[test]
+    fn test_shell_quote() {
+        let samples = vec![
+            ("/home/user/project", "'/home/user/project'"),
+            ("/tmp/my project", "'/tmp/my project'"),
+            ("/tmp/it's", "'/tmp/it'\\''s'"),
+            ("$HOME", "'$HOME'"),
+        ];
+
+        for (input, expected) in samples {
+            assert_eq!(shell_quote(input), expected, "Failed on {}", input);
+        }
+    }
+
+    #[test]
+    fn test_shell_word() {
+        let samples = vec![
+            ("8080", "8080"),
+            ("http://localhost:8080/api", "http://localhost:8080/api"),
+            ("", "''"),
+            ("two words", "'two words'"),
+            ("$HOME", "'$HOME'"),
+        ];
+
+        for (input, expected) in samples {
+            assert_eq!(shell_word(input), expected, "Failed on {}", input);
+        }
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(expand_tilde("~"), home);
+        assert_eq!(expand_tilde("~/code"), home.join("code"));
+        assert_eq!(expand_tilde("/srv/code"), PathBuf::from("/srv/code"));
+        assert_eq!(expand_tilde("~other/code"), PathBuf::from("~other/code"));
+    }
+
+    #[test]
+    fn test_parse_utc_date() {
+        let expected = Utc.with_ymd_and_hms(2023, 9, 23, 12, 0, 0).unwrap();
+
+        assert_eq!(parse_utc_date("2023-09-23T12:00:00Z"), Some(expected));
+        assert_eq!(
+            parse_utc_date("2023-09-23 12:00:00.000000123 UTC"),
+            Some(expected + Duration::nanoseconds(123))
+        );
+        assert_eq!(parse_utc_date("yesterday"), None);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+
+        assert_eq!(format_elapsed(now - Duration::seconds(10), now), "just now");
+        assert_eq!(format_elapsed(now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(format_elapsed(now - Duration::hours(3), now), "3h ago");
+        assert_eq!(format_elapsed(now - Duration::days(2), now), "2d ago");
+        assert_eq!(format_elapsed(now - Duration::days(60), now), "2024-01-10");
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![
+            vec!["api".to_string(), "rust".to_string()],
+            vec!["frontend".to_string(), String::new()],
+        ];
+
+        assert_eq!(
+            format_table(&["NAME", "TYPE"], &rows),
+            "NAME      TYPE\napi       rust\nfrontend\n"
+        );
+    }
 }
@@ -0,0 +1,117 @@
+use std::{fs, path::Path};
+
+#[derive(Debug, PartialEq)]
+pub enum WalkAction {
+    Continue,
+    /// Don't look inside the current directory
+    SkipChildren,
+}
+
+/// Visits the directories under `root` (excluded) up to `max_depth` levels deep.
+///
+/// Hidden directories, symbolic links and directories named like an entry of `ignore` are skipped.
+/// Unreadable directories are silently ignored.
+pub fn walk_dirs<F>(root: &Path, max_depth: usize, ignore: &[String], visit: &mut F)
+where
+    F: FnMut(&Path) -> WalkAction,
+{
+    walk(root, 1, max_depth, ignore, visit);
+}
+
+fn walk<F>(dir: &Path, depth: usize, max_depth: usize, ignore: &[String], visit: &mut F)
+where
+    F: FnMut(&Path) -> WalkAction,
+{
+    if depth > max_depth {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut sub_dirs: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            !name.starts_with('.') && !ignore.contains(&name)
+        })
+        .collect();
+
+    // Keep the walk deterministic
+    sub_dirs.sort();
+
+    for sub_dir in sub_dirs {
+        if visit(&sub_dir) == WalkAction::Continue {
+            walk(&sub_dir, depth + 1, max_depth, ignore, visit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn collect(root: &Path, max_depth: usize, ignore: &[String]) -> Vec<PathBuf> {
+        let mut visited = Vec::new();
+        walk_dirs(root, max_depth, ignore, &mut |path| {
+            visited.push(path.strip_prefix(root).unwrap().to_path_buf());
+            WalkAction::Continue
+        });
+        visited
+    }
+
+    #[test]
+    fn test_walk_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in ["a/b/c", "a/.hidden", "a/node_modules/x", "d"] {
+            fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        fs::write(root.path().join("a/file"), "").unwrap();
+
+        let ignore = vec!["node_modules".to_string()];
+
+        assert_eq!(
+            collect(root.path(), 3, &ignore),
+            vec![
+                PathBuf::from("a"),
+                PathBuf::from("a/b"),
+                PathBuf::from("a/b/c"),
+                PathBuf::from("d")
+            ]
+        );
+        assert_eq!(
+            collect(root.path(), 1, &ignore),
+            vec![PathBuf::from("a"), PathBuf::from("d")]
+        );
+    }
+
+    #[test]
+    fn test_walk_dirs_skip_children() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("a/b")).unwrap();
+        fs::create_dir_all(root.path().join("c/d")).unwrap();
+
+        let mut visited = Vec::new();
+        walk_dirs(root.path(), 5, &[], &mut |path| {
+            visited.push(path.strip_prefix(root.path()).unwrap().to_path_buf());
+            if path.ends_with("a") {
+                WalkAction::SkipChildren
+            } else {
+                WalkAction::Continue
+            }
+        });
+
+        assert_eq!(
+            visited,
+            vec![PathBuf::from("a"), PathBuf::from("c"), PathBuf::from("c/d")]
+        );
+    }
+}
@@ -1,6 +1,15 @@
 use std::{fmt::Debug, path};
 
-use clap::{command, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, CompleteEnv, Shell};
+use commands::{
+    alias::AliasAction,
+    completions::project_name_candidates,
+    config::ConfigAction,
+    init::InitShell,
+    list::{ListFormat, ListSort},
+    tag::TagAction,
+};
 
 mod commands;
 mod config;
@@ -10,8 +19,26 @@ mod models;
 #[derive(Parser, Debug)]
 #[command(name = "cli", version = "1.0", about = "Project Manager CLI")]
 struct Cli {
-    #[arg()]
-    project_name: Option<String>,
+    /// Project to open: a name, an alias or a fuzzy query narrowed by `tag:rust` or `path:work/`.
+    /// A picker over every project is shown when omitted
+    #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+    query: Vec<String>,
+
+    /// Open the project without launching the editor
+    #[arg(long)]
+    no_editor: bool,
+
+    /// Print a `cd` command for the calling shell instead of spawning a subshell
+    #[arg(long)]
+    cd: bool,
+
+    /// Only print the path of the matched project (used by the shell integration)
+    #[arg(long, hide = true)]
+    resolve: bool,
+
+    /// Print the environment variables of the project as `export` commands instead of opening it
+    #[arg(long)]
+    print_env: bool,
 
     #[command(subcommand)]
     command: Option<Commands>,
@@ -25,13 +52,142 @@ enum Commands {
         #[arg(short, long)]
         name: Option<String>,
     },
-    List,
+    /// Clone a git repository into the workspace root and register it
+    Clone {
+        /// Any URL git understands, `file://` URLs and local paths included
+        url: String,
+
+        /// Project name, defaults to the repository name
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Clone there instead of `<clone.root>/<host>/<owner>/<repo>`
+        #[arg(long)]
+        dest: Option<path::PathBuf>,
+    },
+    /// Create a project from a template of `~/.project_manager_cli/templates/` and register it
+    New {
+        template: String,
+        name: String,
+
+        /// Create the project there instead of `./<name>`
+        #[arg(long)]
+        dest: Option<path::PathBuf>,
+
+        /// Run `git init` in the new project
+        #[arg(long)]
+        git: bool,
+    },
+    List {
+        /// Only list the projects of this type (rust, node, python...), can be repeated
+        #[arg(long = "type", value_name = "TYPE")]
+        types: Vec<String>,
+
+        /// Only list the projects having this tag, can be repeated
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Only list the projects whose name matches this glob, or whose path does when it contains a `/`
+        #[arg(long)]
+        filter: Option<String>,
+
+        #[arg(long)]
+        sort: Option<ListSort>,
+
+        /// Show every column
+        #[arg(short, long)]
+        long: bool,
+
+        #[arg(long, default_value = "table")]
+        format: ListFormat,
+    },
     Delete {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
         project_name: String,
     },
+    /// Print the shell integration function, to be evaluated from your shell rc file
+    Init {
+        shell: InitShell,
+
+        /// Name of the generated shell function
+        #[arg(long, default_value = "pm")]
+        cmd: String,
+    },
+    /// Print the completion script for your shell, project names are completed too
+    Completions { shell: Shell },
+    /// Print the metadata and the config of a project
+    Show {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+    },
+    /// Open the config of a project in $EDITOR, the result is validated before being saved
+    Edit {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+    },
+    /// Read or change a key of a project config
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Rename a registered project
+    Rename {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        new_name: String,
+    },
+    /// Update the path of a project after its directory moved
+    Move {
+        #[arg(add = ArgValueCandidates::new(project_name_candidates))]
+        project_name: String,
+        new_path: path::PathBuf,
+    },
+    /// Check the index against the filesystem and the project config files
+    Doctor {
+        /// Prune stale entries, regenerate missing configs and relocate moved projects
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Rebuild a corrupt project index from the projects which still parse
+    Repair,
+    /// Organise projects with tags
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Show the git state of every project: branch, uncommitted changes, upstream, stashes
+    Status {
+        /// Only show the projects with uncommitted changes
+        #[arg(long)]
+        dirty: bool,
+    },
+    /// Give projects alternative names
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
+    /// Register every project found under the given directories
+    Scan {
+        /// Directories to walk, defaults to `discovery.roots` from the config file
+        roots: Vec<path::PathBuf>,
+
+        /// How deep projects are searched, defaults to `discovery.max_depth`
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Only print the projects that would be added
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 fn main() {
+    // Answer the completion requests made by the scripts of the `completions` command,
+    // before the config is initialized so completing never creates or prints anything
+    CompleteEnv::with_factory(Cli::command)
+        .var(crate::commands::completions::COMPLETE_VAR)
+        .complete();
+
     if let Err(e) = crate::config::init_config() {
         eprintln!("Failed to initialize config: {:?}", e);
         std::process::exit(1);
@@ -43,19 +199,104 @@ fn main() {
         Some(Commands::Add { path, name }) => {
             crate::commands::add::execute(path, name);
         }
-        Some(Commands::List) => {
-            crate::commands::list::execute();
+        Some(Commands::Clone { url, name, dest }) => {
+            crate::commands::clone::execute(url, name, dest);
+        }
+        Some(Commands::New {
+            template,
+            name,
+            dest,
+            git,
+        }) => {
+            let options = crate::commands::new::NewOptions {
+                dest: dest.clone(),
+                git_init: *git,
+            };
+            crate::commands::new::execute(template, name, &options);
+        }
+        Some(Commands::List {
+            types,
+            tags,
+            filter,
+            sort,
+            long,
+            format,
+        }) => {
+            let options = crate::commands::list::ListOptions {
+                types: types.clone(),
+                tags: tags.clone(),
+                filter: filter.clone(),
+                sort: *sort,
+                long: *long,
+                format: *format,
+            };
+            crate::commands::list::execute(&options);
         }
         Some(Commands::Delete { project_name }) => {
             crate::commands::delete::execute(project_name);
         }
+        Some(Commands::Init { shell, cmd }) => {
+            crate::commands::init::execute(*shell, cmd);
+        }
+        Some(Commands::Completions { shell }) => {
+            crate::commands::completions::execute(*shell);
+        }
+        Some(Commands::Show { project_name }) => {
+            crate::commands::show::execute(project_name);
+        }
+        Some(Commands::Edit { project_name }) => {
+            crate::commands::edit::execute(project_name);
+        }
+        Some(Commands::Config { action }) => {
+            crate::commands::config::execute(action);
+        }
+        Some(Commands::Rename {
+            project_name,
+            new_name,
+        }) => {
+            crate::commands::rename::execute(project_name, new_name);
+        }
+        Some(Commands::Move {
+            project_name,
+            new_path,
+        }) => {
+            crate::commands::move_project::execute(project_name, new_path);
+        }
+        Some(Commands::Doctor { fix }) => {
+            crate::commands::doctor::execute(*fix);
+        }
+        Some(Commands::Repair) => {
+            crate::commands::repair::execute();
+        }
+        Some(Commands::Tag { action }) => {
+            crate::commands::tag::execute(action);
+        }
+        Some(Commands::Status { dirty }) => {
+            crate::commands::status::execute(*dirty);
+        }
+        Some(Commands::Alias { action }) => {
+            crate::commands::alias::execute(action);
+        }
+        Some(Commands::Scan {
+            roots,
+            depth,
+            dry_run,
+        }) => {
+            let options = crate::commands::scan::ScanOptions {
+                depth: *depth,
+                dry_run: *dry_run,
+            };
+            crate::commands::scan::execute(roots, &options);
+        }
         None => {
-            if let Some(project_name) = cli.project_name {
-                crate::commands::base::execute(project_name);
-            } else {
-                eprintln!("No subcommand or project name provided");
-                std::process::exit(1);
-            }
+            let options = crate::commands::base::OpenOptions {
+                no_editor: cli.no_editor,
+                cd: cli.cd,
+                resolve: cli.resolve,
+                print_env: cli.print_env,
+            };
+            // Without a project name, every project is offered in the picker
+            crate::commands::base::execute(cli.query.join(" "), &options);
         }
     }
 }
@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, fmt, io, path::Path, process::Command};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// Commands run in the project directory, in order, through `sh -c`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Hooks {
+    pub on_open: Vec<String>,
+    pub on_close: Vec<String>,
+    pub on_add: Vec<String>,
+    /// Run once when a project is created by `new`
+    pub on_create: Vec<String>,
+    /// Stop at the first failing command, an `on_open` failure also cancels the opening
+    pub abort_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookStage {
+    Open,
+    Close,
+    Add,
+    Create,
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookStage::Open => write!(f, "on_open"),
+            HookStage::Close => write!(f, "on_close"),
+            HookStage::Add => write!(f, "on_add"),
+            HookStage::Create => write!(f, "on_create"),
+        }
+    }
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        *self == Hooks::default()
+    }
+
+    /// Global hooks run before the project ones, aborting if either of them asks for it.
+    pub fn merge(global: &Hooks, project: &Hooks) -> Hooks {
+        let concat = |a: &[String], b: &[String]| [a, b].concat();
+
+        Hooks {
+            on_open: concat(&global.on_open, &project.on_open),
+            on_close: concat(&global.on_close, &project.on_close),
+            on_add: concat(&global.on_add, &project.on_add),
+            on_create: concat(&global.on_create, &project.on_create),
+            abort_on_failure: global.abort_on_failure || project.abort_on_failure,
+        }
+    }
+
+    pub fn commands(&self, stage: HookStage) -> &[String] {
+        match stage {
+            HookStage::Open => &self.on_open,
+            HookStage::Close => &self.on_close,
+            HookStage::Add => &self.on_add,
+            HookStage::Create => &self.on_create,
+        }
+    }
+
+    /// Runs the commands of `stage`, their output is streamed to the terminal.
+    /// Failures are reported on stderr, an error is returned only when `abort_on_failure` is set.
+    ///
+    /// When `stdout_to_stderr` is set the output of the commands goes to stderr,
+    /// this keeps stdout clean when it is read by the calling shell.
+    pub fn run(
+        &self,
+        stage: HookStage,
+        project_path: &Path,
+        environment: &BTreeMap<String, String>,
+        stdout_to_stderr: bool,
+    ) -> Result<(), anyhow::Error> {
+        for hook in self.commands(stage) {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(hook)
+                .current_dir(project_path)
+                .envs(environment);
+
+            if stdout_to_stderr {
+                command.stdout(io::stderr());
+            }
+
+            let result = command
+                .status()
+                .with_context(|| format!("Failed to run {} hook `{}`", stage, hook))
+                .and_then(|status| {
+                    if status.success() {
+                        Ok(())
+                    } else {
+                        Err(anyhow::anyhow!(
+                            "{} hook `{}` failed ({})",
+                            stage,
+                            hook,
+                            status
+                        ))
+                    }
+                });
+
+            if let Err(e) = result {
+                if self.abort_on_failure {
+                    return Err(e);
+                }
+                eprintln!("Warning: {:?}", e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_merge_hooks() {
+        let global = Hooks {
+            on_open: vec!["git fetch".to_string()],
+            ..Default::default()
+        };
+        let project = Hooks {
+            on_open: vec!["docker compose up -d".to_string()],
+            on_close: vec!["docker compose down".to_string()],
+            abort_on_failure: true,
+            ..Default::default()
+        };
+
+        let hooks = Hooks::merge(&global, &project);
+
+        assert_eq!(hooks.on_open, vec!["git fetch", "docker compose up -d"]);
+        assert_eq!(hooks.on_close, vec!["docker compose down"]);
+        assert!(hooks.on_add.is_empty());
+        assert!(hooks.abort_on_failure);
+    }
+
+    #[test]
+    fn test_run_hooks_in_order() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let hooks = Hooks {
+            on_open: vec![
+                "echo first >> hooks.log".to_string(),
+                "echo \"$GREETING\" >> hooks.log".to_string(),
+            ],
+            ..Default::default()
+        };
+        let environment = BTreeMap::from([("GREETING".to_string(), "hello".to_string())]);
+
+        hooks
+            .run(HookStage::Open, project_dir.path(), &environment, false)
+            .unwrap();
+
+        let log = fs::read_to_string(project_dir.path().join("hooks.log")).unwrap();
+        assert_eq!(log, "first\nhello\n");
+    }
+
+    #[test]
+    fn test_run_hooks_failure() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let mut hooks = Hooks {
+            on_close: vec!["exit 3".to_string(), "touch after_failure".to_string()],
+            ..Default::default()
+        };
+
+        // Failures are only reported by default
+        let result = hooks.run(
+            HookStage::Close,
+            project_dir.path(),
+            &BTreeMap::new(),
+            false,
+        );
+        assert!(result.is_ok());
+        assert!(project_dir.path().join("after_failure").exists());
+
+        fs::remove_file(project_dir.path().join("after_failure")).unwrap();
+        hooks.abort_on_failure = true;
+
+        let result = hooks.run(
+            HookStage::Close,
+            project_dir.path(),
+            &BTreeMap::new(),
+            false,
+        );
+        assert!(result.is_err());
+        assert!(!project_dir.path().join("after_failure").exists());
+    }
+}
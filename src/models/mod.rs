@@ -1,2 +1,5 @@
+pub mod hooks;
 pub mod project_config;
 pub mod project_index;
+pub mod settings;
+pub mod template;
@@ -1,44 +1,173 @@
 use std::{
+    collections::BTreeMap,
+    env,
     fmt::Display,
     fs,
-    io::Write,
     path::{self, Path},
 };
 
 use anyhow::Context;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use cli_project_manager::lib::{
+    atomic_write::write_atomic,
+    detection::{self, PackageInfo},
+    environment, utils,
+};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Debug)]
+use super::{
+    hooks::Hooks,
+    settings::{EditorSettings, ShellSettings},
+};
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct ProjectConfig {
     pub meta_data: ProjectMetaData,
+    /// Replaces the global `[editor]` settings for this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<EditorSettings>,
+    /// Replaces the global `[shell]` settings for this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ShellSettings>,
+    /// Dotenv files, relative to the project path, loaded before `env`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_files: Vec<String>,
+    /// Variables injected in the shell and the editor, `${VAR}` is interpolated.
+    /// The declaration order is kept so a variable can reference the ones declared before it
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub env: IndexMap<String, String>,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
 }
 
 impl ProjectConfig {
     pub fn new(meta_data: ProjectMetaData) -> Self {
-        ProjectConfig { meta_data }
+        ProjectConfig {
+            meta_data,
+            editor: None,
+            shell: None,
+            env_files: Vec::new(),
+            env: IndexMap::new(),
+            hooks: Hooks::default(),
+        }
+    }
+
+    /// Environment of the processes started in the project.
+    ///
+    /// `PROJECT_NAME` and `PROJECT_PATH` come first, then the variables of `env_files` in order
+    /// and finally the `env` table. Values can reference those variables and the current environment.
+    pub fn environment(&self) -> BTreeMap<String, String> {
+        let mut environment = BTreeMap::from([
+            ("PROJECT_NAME".to_string(), self.meta_data.name.clone()),
+            ("PROJECT_PATH".to_string(), self.meta_data.path.clone()),
+        ]);
+
+        for env_file in &self.env_files {
+            let env_file_path = Path::new(&self.meta_data.path).join(env_file);
+
+            let variables = fs::read_to_string(&env_file_path)
+                .context("Failed to read env file")
+                .and_then(|content| {
+                    environment::parse_dotenv(&content, |name| lookup(&environment, name))
+                });
+
+            match variables {
+                Ok(variables) => environment.extend(variables),
+                Err(e) => eprintln!("Warning: {}: {:?}", env_file_path.display(), e),
+            }
+        }
+
+        for (key, value) in &self.env {
+            let value = environment::interpolate(value, |name| lookup(&environment, name));
+            environment.insert(key.clone(), value);
+        }
+
+        environment
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, anyhow::Error> {
+        // Going through `toml::Value` writes plain values before tables whatever the field order
+        let value = toml::Value::try_from(self).context("Failed to serialize project config")?;
+        toml::to_string(&value).context("Failed to serialize project config")
+    }
+
+    /// Strict parsing used to validate user edits, unknown keys are rejected.
+    pub fn from_toml_str(toml_str: &str) -> Result<ProjectConfig, anyhow::Error> {
+        let mut unknown_keys = Vec::new();
+        let mut deserializer = toml::Deserializer::new(toml_str);
+
+        let project_config: ProjectConfig =
+            serde_ignored::deserialize(&mut deserializer, |path| {
+                // Optional tables show up as `?` segments in the path
+                unknown_keys.push(path.to_string().replace(".?", ""))
+            })?;
+
+        if !unknown_keys.is_empty() {
+            return Err(anyhow::anyhow!("Unknown keys: {}", unknown_keys.join(", ")));
+        }
+        project_config.check_env()?;
+
+        Ok(project_config)
+    }
+
+    /// The `env` keys are printed as shell variable names by `--print-env`
+    fn check_env(&self) -> Result<(), anyhow::Error> {
+        match self.env.keys().find(|key| !environment::is_valid_name(key)) {
+            Some(key) => Err(anyhow::anyhow!(
+                "Invalid variable name `{}` in env, expected letters, digits and underscores",
+                key
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn load(project_name: &str) -> Result<ProjectConfig, anyhow::Error> {
+        Self::load_from_path(&project_config_path(project_name))
+    }
+
+    /// Loads the config of a project, or a default one when its file doesn't exist.
+    pub fn load_or_new(meta_data: &ProjectMetaData) -> Result<ProjectConfig, anyhow::Error> {
+        if project_config_path(&meta_data.name).exists() {
+            Self::load(&meta_data.name)
+        } else {
+            Ok(ProjectConfig::new(meta_data.clone()))
+        }
+    }
+
+    fn load_from_path(config_file_path: &Path) -> Result<ProjectConfig, anyhow::Error> {
+        let toml_str = fs::read_to_string(config_file_path)
+            .with_context(|| format!("Failed to read {}", config_file_path.display()))?;
+        let project_config: ProjectConfig = toml::from_str(&toml_str)
+            .with_context(|| format!("Failed to parse {}", config_file_path.display()))?;
+        project_config
+            .check_env()
+            .with_context(|| format!("Failed to parse {}", config_file_path.display()))?;
+
+        Ok(project_config)
     }
 
     // TODO Add error handling
     pub fn save(&self) -> Result<(), anyhow::Error> {
-        let toml_str = toml::to_string(&self).context("Failed to serialize project config")?;
+        let toml_str = self.to_toml_string()?;
         let project_config_file_path = project_config_path(&self.meta_data.name);
+        let quiet = crate::config::get_config()
+            .unwrap()
+            .read()
+            .unwrap()
+            .settings
+            .output
+            .quiet;
 
-        println!(
-            "Path file: {:?}",
-            project_config_file_path.parent().unwrap().exists()
-        );
-        let mut config_file = fs::File::create(project_config_file_path)
-            .context("Failed creating  project config file")?;
-
-        config_file
-            .write_all(toml_str.as_bytes())
+        write_atomic(&project_config_file_path, toml_str.as_bytes())
             .context("Failed to write the config file")?;
 
-        println!(
-            "Project config file created at : ~/.project_manager_cli/projects/{}.toml",
-            self.meta_data.name
-        );
+        if !quiet {
+            println!(
+                "Project config file created at : ~/.project_manager_cli/projects/{}.toml",
+                self.meta_data.name
+            );
+        }
 
         Ok(())
     }
@@ -58,7 +187,14 @@ impl ProjectConfig {
     }
 }
 
-fn project_config_path(project_name: &str) -> path::PathBuf {
+fn lookup(environment: &BTreeMap<String, String>, name: &str) -> Option<String> {
+    environment
+        .get(name)
+        .cloned()
+        .or_else(|| env::var(name).ok())
+}
+
+pub fn project_config_path(project_name: &str) -> path::PathBuf {
     let config = crate::config::get_config().unwrap().read().unwrap();
     config
         .base_dir
@@ -66,11 +202,26 @@ fn project_config_path(project_name: &str) -> path::PathBuf {
         .clone()
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ProjectMetaData {
     pub name: String,
     pub creation_date_utc: String,
     pub path: String,
+    /// Languages and toolchains detected from the manifest files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Alternative names opening the project, resolved before fuzzy matching
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_opened_utc: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub open_count: u32,
+    // Tables must be serialized after the plain values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<PackageInfo>,
 }
 
 impl ProjectMetaData {
@@ -85,12 +236,65 @@ impl ProjectMetaData {
                 .to_string()
         });
 
+        let detection = detection::detect(&path);
+
         Ok(ProjectMetaData {
             name,
             path: canonical_path,
             creation_date_utc: Utc::now().to_string(),
+            types: detection.types,
+            tags: Vec::new(),
+            aliases: Vec::new(),
+            last_opened_utc: None,
+            open_count: 0,
+            package: detection.package,
         })
     }
+
+    /// A project with fixed dates and a path under `/srv`, for tests.
+    /// Other fields are set with the struct update syntax.
+    #[cfg(test)]
+    pub fn sample(name: &str) -> Self {
+        ProjectMetaData {
+            name: name.to_string(),
+            creation_date_utc: "2023-09-23T12:00:00Z".to_string(),
+            path: format!("/srv/{}", name),
+            types: Vec::new(),
+            tags: Vec::new(),
+            aliases: Vec::new(),
+            last_opened_utc: None,
+            open_count: 0,
+            package: None,
+        }
+    }
+
+    /// Ranks the projects by how often and how recently they were opened, like `z` does.
+    pub fn frecency(&self, now: DateTime<Utc>) -> f64 {
+        let Some(last_opened) = self
+            .last_opened_utc
+            .as_deref()
+            .and_then(utils::parse_utc_date)
+        else {
+            return 0.0;
+        };
+
+        let elapsed = now.signed_duration_since(last_opened);
+        let weight = if elapsed.num_hours() < 1 {
+            4.0
+        } else if elapsed.num_days() < 1 {
+            2.0
+        } else if elapsed.num_weeks() < 1 {
+            0.5
+        } else {
+            0.25
+        };
+
+        f64::from(self.open_count) * weight
+    }
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
 }
 
 impl Display for ProjectMetaData {
@@ -104,20 +308,24 @@ impl PartialEq for ProjectMetaData {
         self.name == other.name
             && self.creation_date_utc == other.creation_date_utc
             && self.path == other.path
+            && self.types == other.types
+            && self.tags == other.tags
+            && self.aliases == other.aliases
+            && self.package == other.package
     }
 }
 
 #[cfg(test)]
 mod test {
 
-    use std::io::Cursor;
+    use std::io::{Cursor, Write};
 
     use super::*;
 
     #[test]
     fn create_project_meta_data() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let project_path = temp_dir.into_path();
+        let project_path = temp_dir.keep();
 
         let project_meta_data =
             ProjectMetaData::new(&project_path, Some("project_name".to_string())).unwrap();
@@ -136,10 +344,10 @@ mod test {
     #[test]
     fn create_project_meta_data_without_name() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let project_path = temp_dir.into_path();
+        let project_path = temp_dir.keep();
         let project_name = project_path
-            .into_iter()
-            .last()
+            .iter()
+            .next_back()
             .unwrap()
             .to_string_lossy()
             .to_string();
@@ -157,11 +365,51 @@ mod test {
         );
     }
 
+    #[test]
+    fn create_project_meta_data_detects_type() {
+        let project_path = tempfile::tempdir().unwrap();
+        fs::write(
+            project_path.path().join("Cargo.toml"),
+            "[package]\nname = \"my_crate\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let project_meta_data = ProjectMetaData::new(project_path.path(), None).unwrap();
+
+        assert_eq!(project_meta_data.types, vec!["rust"]);
+        assert_eq!(
+            project_meta_data.package.as_ref().unwrap().version,
+            Some("0.1.0".to_string())
+        );
+
+        // Detected info goes through the config file
+        let project_config = ProjectConfig::new(project_meta_data.clone());
+        let loaded =
+            ProjectConfig::from_toml_str(&project_config.to_toml_string().unwrap()).unwrap();
+        assert_eq!(loaded.meta_data.types, project_meta_data.types);
+        assert_eq!(loaded.meta_data.package, project_meta_data.package);
+    }
+
+    #[test]
+    fn project_meta_data_frecency() {
+        let now = Utc::now();
+        let mut project_meta_data =
+            ProjectMetaData::new(&tempfile::tempdir().unwrap().keep(), None).unwrap();
+
+        assert_eq!(project_meta_data.frecency(now), 0.0);
+
+        project_meta_data.open_count = 10;
+        project_meta_data.last_opened_utc = Some((now - chrono::Duration::minutes(5)).to_string());
+        assert_eq!(project_meta_data.frecency(now), 40.0);
+
+        project_meta_data.last_opened_utc = Some((now - chrono::Duration::days(30)).to_string());
+        assert_eq!(project_meta_data.frecency(now), 2.5);
+    }
+
     #[test]
     #[should_panic]
     fn create_project_meta_data_with_invalide_path() {
-        let invalide_path =
-            path::PathBuf::new().join("/tmp/jflsdjflksdjflkjslfjlsdfbdshjkgvbnjkhcvfh");
+        let invalide_path = path::PathBuf::from("/tmp/jflsdjflksdjflkjslfjlsdfbdshjkgvbnjkhcvfh");
 
         let _ = ProjectMetaData::new(&invalide_path, None).unwrap();
     }
@@ -176,7 +424,7 @@ mod test {
     #[test]
     fn create_project_and_save() {
         let project_name = Some("test_project".to_string());
-        let project_path = tempfile::tempdir().unwrap().into_path();
+        let project_path = tempfile::tempdir().unwrap().keep();
         let project_meta_data = ProjectMetaData::new(&project_path, project_name.clone())
             .expect("Failed to create project metadata");
 
@@ -191,11 +439,169 @@ mod test {
         println!("Test passed: Porject config serialized and mock savec correctly");
     }
 
+    #[test]
+    fn load_project_config_with_editor_override() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_file_path = temp_dir.path().join("project.toml");
+
+        let sample_data = r#"
+            [meta_data]
+            name = "TestProject"
+            creation_date_utc = "2023-09-23T12:00:00Z"
+            path = "some/path"
+
+            [editor]
+            command = "nvim"
+            wait = true
+        "#;
+        fs::write(&config_file_path, sample_data).unwrap();
+
+        let project_config = ProjectConfig::load_from_path(&config_file_path).unwrap();
+        let editor = project_config.editor.unwrap();
+
+        assert_eq!(project_config.meta_data.name, "TestProject");
+        assert_eq!(editor.command, "nvim");
+        assert!(editor.wait);
+        assert_eq!(editor.args, vec!["{path}"]);
+    }
+
+    #[test]
+    fn load_project_config_without_override() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_file_path = temp_dir.path().join("project.toml");
+
+        let sample_data = r#"
+            [meta_data]
+            name = "TestProject"
+            creation_date_utc = "2023-09-23T12:00:00Z"
+            path = "some/path"
+        "#;
+        fs::write(&config_file_path, sample_data).unwrap();
+
+        let project_config = ProjectConfig::load_from_path(&config_file_path).unwrap();
+
+        assert!(project_config.editor.is_none());
+    }
+
+    #[test]
+    fn project_config_environment() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let project_meta_data =
+            ProjectMetaData::new(project_dir.path(), Some("env_project".to_string())).unwrap();
+        let project_path = project_meta_data.path.clone();
+
+        fs::write(
+            project_dir.path().join(".env"),
+            "DB_HOST=localhost\nDB_URL=postgres://${DB_HOST}/${PROJECT_NAME}\n",
+        )
+        .unwrap();
+
+        let mut project_config = ProjectConfig::new(project_meta_data);
+        project_config.env_files = vec![".env".to_string(), ".env.missing".to_string()];
+        project_config.env = IndexMap::from([
+            ("DATA_DIR".to_string(), "${PROJECT_PATH}/data".to_string()),
+            ("DB_HOST".to_string(), "db.local".to_string()),
+            ("CACHE_DIR".to_string(), "${DATA_DIR}/cache".to_string()),
+            ("ARCHIVE_DIR".to_string(), "${BACKUP_DIR:-none}".to_string()),
+            ("BACKUP_DIR".to_string(), "/backup".to_string()),
+        ]);
+
+        let environment = project_config.environment();
+
+        assert_eq!(environment["PROJECT_NAME"], "env_project");
+        assert_eq!(environment["DB_URL"], "postgres://localhost/env_project");
+        assert_eq!(environment["DB_HOST"], "db.local");
+        assert_eq!(environment["DATA_DIR"], format!("{}/data", project_path));
+        // Variables of the table are visible to the ones declared after them only
+        assert_eq!(
+            environment["CACHE_DIR"],
+            format!("{}/data/cache", project_path)
+        );
+        assert_eq!(environment["ARCHIVE_DIR"], "none");
+    }
+
+    #[test]
+    fn project_config_with_env_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_file_path = temp_dir.path().join("project.toml");
+        let project_meta_data =
+            ProjectMetaData::new(temp_dir.path(), Some("env_project".to_string())).unwrap();
+
+        let mut project_config = ProjectConfig::new(project_meta_data);
+        project_config.env_files = vec![".env".to_string()];
+        project_config.env = IndexMap::from([("RUST_LOG".to_string(), "debug".to_string())]);
+
+        fs::write(&config_file_path, project_config.to_toml_string().unwrap()).unwrap();
+        let loaded = ProjectConfig::load_from_path(&config_file_path).unwrap();
+
+        assert_eq!(loaded.env_files, vec![".env"]);
+        assert_eq!(loaded.env["RUST_LOG"], "debug");
+    }
+
+    #[test]
+    fn project_config_keeps_the_env_declaration_order() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_file_path = temp_dir.path().join("project.toml");
+        let project_meta_data =
+            ProjectMetaData::new(temp_dir.path(), Some("env_project".to_string())).unwrap();
+
+        let mut project_config = ProjectConfig::new(project_meta_data);
+        project_config.env = IndexMap::from([
+            ("ROOT".to_string(), "/srv".to_string()),
+            ("BIN".to_string(), "${ROOT}/bin".to_string()),
+        ]);
+
+        fs::write(&config_file_path, project_config.to_toml_string().unwrap()).unwrap();
+        let loaded = ProjectConfig::load_from_path(&config_file_path).unwrap();
+
+        assert_eq!(loaded.env.keys().collect::<Vec<_>>(), vec!["ROOT", "BIN"]);
+        assert_eq!(loaded.environment()["BIN"], "/srv/bin");
+    }
+
+    #[test]
+    fn strict_parsing_rejects_unknown_keys() {
+        let sample_data = r#"
+            [meta_data]
+            name = "TestProject"
+            creation_date_utc = "2023-09-23T12:00:00Z"
+            path = "some/path"
+
+            [editor]
+            comand = "nvim"
+        "#;
+
+        let error = ProjectConfig::from_toml_str(sample_data).unwrap_err();
+        assert!(error.to_string().contains("editor.comand"));
+
+        let valid = sample_data.replace("comand", "command");
+        let project_config = ProjectConfig::from_toml_str(&valid).unwrap();
+        assert_eq!(project_config.editor.unwrap().command, "nvim");
+    }
+
+    #[test]
+    fn env_keys_must_be_shell_variable_names() {
+        let sample_data = r#"
+            [meta_data]
+            name = "TestProject"
+            creation_date_utc = "2023-09-23T12:00:00Z"
+            path = "some/path"
+
+            [env]
+            "X;echo INJECTED;Y" = "1"
+        "#;
+
+        let error = ProjectConfig::from_toml_str(sample_data).unwrap_err();
+        assert!(error.to_string().contains("X;echo INJECTED;Y"));
+
+        let valid = sample_data.replace("X;echo INJECTED;Y", "INJECTED");
+        assert!(ProjectConfig::from_toml_str(&valid).is_ok());
+    }
+
     #[test]
     fn test_project_config_file_path() {
         crate::config::init_config().unwrap();
         let project_name = "test_project";
-        let project_config_path = project_config_path(&project_name);
+        let project_config_path = project_config_path(project_name);
 
         let config = crate::config::get_config().unwrap().read().unwrap();
 
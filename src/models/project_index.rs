@@ -1,47 +1,190 @@
 use std::{
-    fmt, fs,
-    path::{self},
+    fmt, fs, io,
+    path::{self, Path, PathBuf},
+    time::Duration,
 };
 
-use super::project_config::{ProjectConfig, ProjectMetaData};
+use super::project_config::{project_config_path, ProjectConfig, ProjectMetaData};
 use anyhow::Context;
+use chrono::Utc;
+use cli_project_manager::lib::{atomic_write::write_atomic, file_lock::FileLock, utils};
 use serde::{Deserialize, Serialize};
 
+/// Sum of the open counts above which every count is reduced by 10%
+const MAX_TOTAL_OPENS: u32 = 1000;
+
+/// How long a command waits for another one to finish modifying the index
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ProjectIndex {
     pub projects: Vec<ProjectMetaData>,
+    /// Held until the index is dropped when it was loaded for an update
+    #[serde(skip)]
+    lock: Option<FileLock>,
+    /// Set when the file couldn't be parsed, writing would wipe the projects it still contains
+    #[serde(skip)]
+    corrupt: bool,
+}
+
+/// Why the index file couldn't be loaded
+#[derive(Debug)]
+pub enum IndexLoadError {
+    /// There is no index yet, it is the only case where a new index may replace it
+    NotFound,
+    Corrupt(CorruptIndexError),
+}
+
+impl fmt::Display for IndexLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexLoadError::NotFound => write!(f, "Project index file not found"),
+            IndexLoadError::Corrupt(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for IndexLoadError {}
+
+/// The index file exists but can't be read or doesn't parse, it must be repaired rather than replaced
+#[derive(Debug)]
+pub struct CorruptIndexError {
+    path: PathBuf,
+    error: String,
+}
+
+impl fmt::Display for CorruptIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The project index {} is corrupt: {}\n\
+             The previous version is kept in {}, run `repair` to salvage the projects that still parse",
+            self.path.display(),
+            self.error,
+            backup_path(&self.path).display()
+        )
+    }
+}
+
+impl std::error::Error for CorruptIndexError {}
+
+/// Outcome of `ProjectIndex::repair`
+#[derive(Debug, Default, PartialEq)]
+pub struct RepairReport {
+    pub salvaged: Vec<String>,
+    /// Entries which didn't parse or duplicated a salvaged name
+    pub discarded: usize,
+    /// Where the corrupt file was moved, `None` when the index was valid
+    pub corrupt_copy: Option<PathBuf>,
 }
 
 impl ProjectIndex {
     pub fn new() -> Self {
         ProjectIndex {
             projects: Vec::new(),
+            lock: None,
+            corrupt: false,
         }
     }
 
-    pub fn load() -> Result<ProjectIndex, anyhow::Error> {
+    /// Loads the index for a read-modify-write cycle, the other commands modifying it wait until it is dropped.
+    /// Reading doesn't need the lock as the index is replaced atomically.
+    pub fn load_for_update() -> Result<ProjectIndex, anyhow::Error> {
+        let lock = lock_index()?;
+        let mut project_index = match Self::load() {
+            Ok(project_index) => project_index,
+            Err(IndexLoadError::NotFound) => Self::new(),
+            Err(e) => return Err(e.into()),
+        };
+        project_index.lock = Some(lock);
+        Ok(project_index)
+    }
+
+    pub fn load() -> Result<ProjectIndex, IndexLoadError> {
         let config = crate::config::get_config().unwrap().read().unwrap();
 
         Self::load_from_path(&config.project_index_file)
     }
 
-    fn load_from_path(index_file_path: &path::PathBuf) -> Result<ProjectIndex, anyhow::Error> {
-        if index_file_path.exists() {
-            let toml_str =
-                fs::read_to_string(index_file_path).context("Failed to read project index")?;
-            let projet_index =
-                toml::from_str(&toml_str).context("Failed to parse project index file")?;
-            Ok(projet_index)
-        } else {
-            Err(anyhow::anyhow!("Project index file not found"))
+    /// An empty file is a new index. A file which can't be read, isn't UTF-8 or doesn't parse is corrupt.
+    pub fn load_from_path(index_file_path: &path::PathBuf) -> Result<ProjectIndex, IndexLoadError> {
+        let corrupt = |error: String| {
+            IndexLoadError::Corrupt(CorruptIndexError {
+                path: index_file_path.clone(),
+                error,
+            })
+        };
+
+        let toml_str = match fs::read_to_string(index_file_path) {
+            Ok(toml_str) => toml_str,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(IndexLoadError::NotFound),
+            Err(e) => return Err(corrupt(e.to_string())),
+        };
+        if toml_str.trim().is_empty() {
+            return Ok(Self::new());
         }
+
+        toml::from_str(&toml_str).map_err(|e| corrupt(e.to_string()))
     }
 
+    /// A missing index is a new one. A corrupt index is reported and loaded empty and read only.
     pub fn load_or_new() -> ProjectIndex {
         match Self::load() {
             Ok(p_index) => p_index,
-            Err(_) => Self::new(),
+            Err(IndexLoadError::NotFound) => Self::new(),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ProjectIndex {
+                    corrupt: true,
+                    ..Self::new()
+                }
+            }
+        }
+    }
+
+    /// Rebuilds a corrupt index from its projects which still parse.
+    /// The corrupt file is kept next to the index and the backup is left untouched.
+    pub fn repair() -> Result<RepairReport, anyhow::Error> {
+        let lock = lock_index()?;
+        let index_file_path = crate::config::get_config()
+            .unwrap()
+            .read()
+            .unwrap()
+            .project_index_file
+            .clone();
+
+        match Self::load_from_path(&index_file_path) {
+            Err(IndexLoadError::Corrupt(_)) => {}
+            // Nothing to repair
+            _ => return Ok(RepairReport::default()),
         }
+
+        // Invalid UTF-8 only spoils the entries it appears in
+        let content = fs::read(&index_file_path).context("Failed to read project index")?;
+        let (projects, discarded) = salvage(&String::from_utf8_lossy(&content));
+
+        let mut corrupt_copy = index_file_path.as_os_str().to_owned();
+        corrupt_copy.push(".corrupt");
+        let corrupt_copy = PathBuf::from(corrupt_copy);
+        write_atomic(&corrupt_copy, &content)
+            .context("Failed to keep a copy of the corrupt index")?;
+
+        let project_index = ProjectIndex {
+            projects,
+            lock: Some(lock),
+            corrupt: false,
+        };
+        project_index.write()?;
+
+        Ok(RepairReport {
+            salvaged: project_index
+                .projects
+                .iter()
+                .map(|project| project.name.clone())
+                .collect(),
+            discarded,
+            corrupt_copy: Some(corrupt_copy),
+        })
     }
 
     pub fn add_project_and_save(
@@ -51,29 +194,153 @@ impl ProjectIndex {
         if self.project_exists(&new_project.name, &new_project.path) {
             return Err(anyhow::anyhow!("Project already exists"));
         }
+        self.check_new_project(&new_project)?;
 
         self.projects.push(new_project);
         self.save()?;
 
         Ok(())
     }
+    /// Adds several projects with a single write of the index, none is added if one already exists.
+    pub fn add_projects_and_save(
+        &mut self,
+        new_projects: Vec<ProjectMetaData>,
+    ) -> Result<(), anyhow::Error> {
+        let previous_len = self.projects.len();
+
+        for new_project in new_projects {
+            if let Err(e) = self.check_new_project(&new_project) {
+                self.projects.truncate(previous_len);
+                return Err(e);
+            }
+            self.projects.push(new_project);
+        }
+
+        if let Err(e) = self.save() {
+            self.projects.truncate(previous_len);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Fails when `name` already opens a project, names and aliases share one namespace.
+    pub fn ensure_name_available(&self, name: &str) -> Result<(), anyhow::Error> {
+        check_name(name)?;
+        if self.find_project_by_name(name).is_ok() {
+            return Err(anyhow::anyhow!("{} is already a project name", name));
+        }
+        if let Some(project) = self.find_project_by_alias(name) {
+            return Err(anyhow::anyhow!(
+                "{} is already an alias of {}",
+                name,
+                project.name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that a project which is not part of the index can join it:
+    /// its path must not be registered and neither its name nor its aliases may be taken.
+    pub fn check_new_project(&self, project: &ProjectMetaData) -> Result<(), anyhow::Error> {
+        if let Some(other) = self.projects.iter().find(|p| p.path == project.path) {
+            return Err(anyhow::anyhow!(
+                "{} is already registered as {}",
+                project.path,
+                other.name
+            ));
+        }
+
+        for name in std::iter::once(&project.name).chain(&project.aliases) {
+            self.ensure_name_available(name)?;
+        }
+        if project.aliases.contains(&project.name) {
+            return Err(anyhow::anyhow!(
+                "{} can't be both the name and an alias of the project",
+                project.name
+            ));
+        }
+        Ok(())
+    }
+
     pub fn project_exists(&self, name: &str, path: &str) -> bool {
         self.projects
             .iter()
             .any(|p| p.name == name || p.path == path)
     }
 
+    /// Records an opening of a project, only the index is written since usage data isn't configuration.
+    /// Nothing is printed as stdout may be read by the shell integration.
+    pub fn record_open_and_save(&mut self, project_name: &str) -> Result<(), anyhow::Error> {
+        // The index may have changed since it was loaded, while the picker was shown for instance
+        if self.lock.is_none() {
+            let mut project_index = Self::load_for_update()?;
+            project_index.record_open_and_save(project_name)?;
+            self.projects = project_index.projects;
+            return Ok(());
+        }
+
+        let project = self
+            .projects
+            .iter_mut()
+            .find(|p| p.name == project_name)
+            .context(format!("Project {} not found", project_name))?;
+
+        project.last_opened_utc = Some(Utc::now().to_string());
+        project.open_count += 1;
+
+        // Age the counts like `z` does so old habits fade away
+        let total_opens: u32 = self.projects.iter().map(|p| p.open_count).sum();
+        if total_opens > MAX_TOTAL_OPENS {
+            for project in self.projects.iter_mut() {
+                project.open_count = project.open_count * 9 / 10;
+            }
+        }
+
+        self.write()
+    }
+
+    /// The most recently opened project
+    pub fn last_opened_project(&self) -> Option<&ProjectMetaData> {
+        self.projects
+            .iter()
+            .filter_map(|project| {
+                let last_opened = utils::parse_utc_date(project.last_opened_utc.as_deref()?)?;
+                Some((last_opened, project))
+            })
+            .max_by_key(|(last_opened, _)| *last_opened)
+            .map(|(_, project)| project)
+    }
+
     fn save(&self) -> Result<(), anyhow::Error> {
-        let toml_str = toml::to_string(&self).context("Failed to serialize project index")?;
+        self.write()?;
+
+        let config = crate::config::get_config().unwrap().read().unwrap();
+        if !config.settings.output.quiet {
+            println!("Project index file saved");
+        }
+        Ok(())
+    }
+
+    fn write(&self) -> Result<(), anyhow::Error> {
+        if self.corrupt {
+            return Err(anyhow::anyhow!(
+                "The project index is corrupt, refusing to overwrite it, run `repair` first"
+            ));
+        }
+
+        // Going through `toml::Value` puts the nested tables after the plain values
+        let toml_str = toml::Value::try_from(self)
+            .and_then(|value| toml::to_string(&value))
+            .context("Failed to serialize project index")?;
 
         let config = crate::config::get_config().unwrap().read().unwrap();
 
         fs::create_dir_all(config.project_index_file.parent().unwrap())
             .context("Failed to create directory for project index file")?;
 
-        fs::write(&config.project_index_file, &toml_str)
+        backup(&config.project_index_file)?;
+        write_atomic(&config.project_index_file, toml_str.as_bytes())
             .context("Failed to write project index file")?;
-        println!("Project index file saved");
         Ok(())
     }
 
@@ -87,6 +354,66 @@ impl ProjectIndex {
             .context(format!("Project {} not found", project_name))
     }
 
+    pub fn find_project_by_alias(&self, alias: &str) -> Option<&ProjectMetaData> {
+        self.projects
+            .iter()
+            .find(|project| project.aliases.iter().any(|a| a == alias))
+    }
+
+    /// Applies `update` to the metadata of a project then saves the index and the project config.
+    /// The config file is renamed along with the project, if saving fails the previous state is restored.
+    pub fn update_project_and_save<F>(
+        &mut self,
+        project_name: &str,
+        update: F,
+    ) -> Result<(), anyhow::Error>
+    where
+        F: FnOnce(&mut ProjectMetaData),
+    {
+        let position = self
+            .projects
+            .iter()
+            .position(|p| p.name == project_name)
+            .context(format!("Project {} not found", project_name))?;
+
+        let previous = self.projects.remove(position);
+        let mut updated = previous.clone();
+        update(&mut updated);
+
+        // Collisions are checked against the other projects only
+        let collision = self.check_new_project(&updated);
+        self.projects.insert(position, previous.clone());
+        collision?;
+
+        let previous_config = ProjectConfig::load_or_new(&previous)?;
+        let mut updated_config = ProjectConfig::load_or_new(&previous)?;
+        updated_config.meta_data = updated.clone();
+        updated_config.save()?;
+
+        let renamed = previous.name != updated.name;
+        let updated_config_path = project_config_path(&updated.name);
+        self.projects[position] = updated;
+
+        if let Err(e) = self.save() {
+            self.projects[position] = previous;
+            if renamed {
+                let _ = fs::remove_file(updated_config_path);
+            }
+            let _ = previous_config.save();
+            return Err(e);
+        }
+
+        if renamed {
+            let previous_config_path = project_config_path(&previous.name);
+            if previous_config_path.exists() {
+                fs::remove_file(previous_config_path)
+                    .context("Failed to remove the previous project config file")?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn remove_project_by_name(&mut self, project_name: &str) -> Result<(), anyhow::Error> {
         let project_index = self
             .projects
@@ -96,8 +423,11 @@ impl ProjectIndex {
 
         let project_meta_data = self.projects.remove(project_index);
 
-        let project_config = ProjectConfig::new(project_meta_data);
-        project_config.remove()?;
+        // The config file may already be gone, the index entry must be removed anyway
+        if project_config_path(&project_meta_data.name).exists() {
+            let project_config = ProjectConfig::new(project_meta_data);
+            project_config.remove()?;
+        }
         self.save()?;
         Ok(())
     }
@@ -112,6 +442,105 @@ impl fmt::Display for ProjectIndex {
     }
 }
 
+/// Names and aliases become config file names (`projects/<name>.toml`) and directory names
+pub fn check_name(name: &str) -> Result<(), anyhow::Error> {
+    if name.is_empty() || name.starts_with('.') || name.contains('/') {
+        return Err(anyhow::anyhow!(
+            "Invalid name `{}`, names can't be empty, start with a dot or contain a slash",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Parses every `[[projects]]` entry on its own, returns the valid ones and the number of discarded entries.
+fn salvage(toml_str: &str) -> (Vec<ProjectMetaData>, usize) {
+    let mut entries: Vec<String> = vec![String::new()];
+    for line in toml_str.lines() {
+        if line.trim_start().starts_with("[[projects]]") {
+            entries.push(String::new());
+        }
+        let entry = entries.last_mut().unwrap();
+        entry.push_str(line);
+        entry.push('\n');
+    }
+
+    let mut projects: Vec<ProjectMetaData> = Vec::new();
+    let mut discarded = 0;
+
+    // The first entry holds what comes before the first project, usually nothing
+    let preamble_is_blank = entries[0]
+        .lines()
+        .all(|line| line.trim().is_empty() || line.trim_start().starts_with('#'));
+    if !preamble_is_blank {
+        discarded += 1;
+    }
+
+    for entry in &entries[1..] {
+        match toml::from_str::<ProjectIndex>(entry) {
+            Ok(parsed) => {
+                for project in parsed.projects {
+                    if projects.iter().any(|p| p.name == project.name) {
+                        discarded += 1;
+                    } else {
+                        projects.push(project);
+                    }
+                }
+            }
+            Err(_) => discarded += 1,
+        }
+    }
+
+    (projects, discarded)
+}
+
+fn lock_index() -> Result<FileLock, anyhow::Error> {
+    let lock_path = {
+        let config = crate::config::get_config().unwrap().read().unwrap();
+        let mut file_name = config.project_index_file.as_os_str().to_owned();
+        file_name.push(".lock");
+        PathBuf::from(file_name)
+    };
+
+    FileLock::acquire(&lock_path, LOCK_TIMEOUT).map_err(|e| match e.kind() {
+        io::ErrorKind::TimedOut => anyhow::anyhow!(
+            "Another command is modifying the project index, gave up after waiting {}s",
+            LOCK_TIMEOUT.as_secs()
+        ),
+        _ => anyhow::Error::from(e).context("Failed to lock the project index"),
+    })
+}
+
+/// Path of the copy of the previous index, kept to recover from a bad write or a bad edit
+pub fn backup_path(index_file_path: &Path) -> PathBuf {
+    let mut file_name = index_file_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".bak");
+    index_file_path.with_file_name(file_name)
+}
+
+/// Copies the current index to its backup before it is replaced.
+/// An index that doesn't parse is not backed up, so it never overwrites a good backup.
+fn backup(index_file_path: &Path) -> Result<(), anyhow::Error> {
+    let Ok(previous) = fs::read_to_string(index_file_path) else {
+        return Ok(());
+    };
+
+    if toml::from_str::<ProjectIndex>(&previous).is_err() {
+        return Ok(());
+    }
+
+    let backup_file_path = backup_path(index_file_path);
+    write_atomic(&backup_file_path, previous.as_bytes())
+        .context("Failed to back up the project index")?;
+
+    // The backup is as private as the index
+    if let Ok(metadata) = fs::metadata(index_file_path) {
+        fs::set_permissions(&backup_file_path, metadata.permissions())
+            .context("Failed to back up the project index")?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use std::io::Write;
@@ -129,11 +558,11 @@ mod test {
     #[test]
     fn test_load_from_path_non_existent_file_index() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let index_file_path = temp_dir.into_path().join("non_existent_index_file.toml");
+        let index_file_path = temp_dir.keep().join("non_existent_index_file.toml");
 
         let result = ProjectIndex::load_from_path(&index_file_path);
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(IndexLoadError::NotFound)));
     }
 
     #[test]
@@ -150,7 +579,7 @@ mod test {
             path = "some/path"
         "#;
 
-        file.write_all(&sample_data.as_bytes()).unwrap();
+        file.write_all(sample_data.as_bytes()).unwrap();
 
         let result = ProjectIndex::load_from_path(&index_file_path);
 
@@ -165,7 +594,7 @@ mod test {
     #[test]
     fn test_project_exists() {
         let project_name = Some("project_test".to_string());
-        let project_path = tempfile::tempdir().unwrap().into_path();
+        let project_path = tempfile::tempdir().unwrap().keep();
 
         let project_meta_data = ProjectMetaData::new(&project_path, project_name.clone()).unwrap();
 
@@ -198,5 +627,188 @@ mod test {
         assert_eq!(*project_meta_data_finded, project_index.projects[0]);
     }
 
+    #[test]
+    fn test_names_and_aliases_share_one_namespace() {
+        let mut project_index = ProjectIndex::new();
+        let mut api = ProjectMetaData::new(
+            &tempfile::tempdir().unwrap().keep(),
+            Some("api".to_string()),
+        )
+        .unwrap();
+        api.aliases = vec!["backend".to_string()];
+        project_index.projects.push(api.clone());
+
+        assert!(project_index.ensure_name_available("web").is_ok());
+        for invalid in ["", ".", "..", ".hidden", "a/b", "../escape"] {
+            assert!(
+                project_index.ensure_name_available(invalid).is_err(),
+                "Failed on {}",
+                invalid
+            );
+        }
+        assert!(project_index.ensure_name_available("api").is_err());
+        assert!(project_index.ensure_name_available("backend").is_err());
+
+        let mut web = ProjectMetaData::new(
+            &tempfile::tempdir().unwrap().keep(),
+            Some("web".to_string()),
+        )
+        .unwrap();
+        assert!(project_index.check_new_project(&web).is_ok());
+
+        web.name = "backend".to_string();
+        assert!(project_index.check_new_project(&web).is_err());
+
+        web.name = "web".to_string();
+        web.aliases = vec!["api".to_string()];
+        assert!(project_index.check_new_project(&web).is_err());
+
+        web.aliases = vec!["web".to_string()];
+        assert!(project_index.check_new_project(&web).is_err());
+
+        web.aliases.clear();
+        web.path = api.path.clone();
+        assert!(project_index.check_new_project(&web).is_err());
+    }
+
+    #[test]
+    fn test_last_opened_project() {
+        let mut project_index = ProjectIndex::new();
+        assert!(project_index.last_opened_project().is_none());
+
+        for (name, last_opened) in [
+            ("old", Some("2024-01-01T10:00:00Z")),
+            ("recent", Some("2024-03-01 10:00:00.0 UTC")),
+            ("never", None),
+        ] {
+            let mut project =
+                ProjectMetaData::new(&tempfile::tempdir().unwrap().keep(), Some(name.to_string()))
+                    .unwrap();
+            project.last_opened_utc = last_opened.map(|date| date.to_string());
+            project_index.projects.push(project);
+        }
+
+        assert_eq!(project_index.last_opened_project().unwrap().name, "recent");
+    }
+
     // Remove project
+
+    #[test]
+    fn test_backup_keeps_the_last_valid_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index_file_path = temp_dir.path().join("project_index.toml");
+        let backup_file_path = backup_path(&index_file_path);
+        assert_eq!(
+            backup_file_path,
+            temp_dir.path().join("project_index.toml.bak")
+        );
+
+        // Nothing to back up yet
+        backup(&index_file_path).unwrap();
+        assert!(!backup_file_path.exists());
+
+        let valid_index = "[[projects]]\nname = \"TestProject\"\ncreation_date_utc = \"2023-09-23T12:00:00Z\"\npath = \"some/path\"\n";
+        fs::write(&index_file_path, valid_index).unwrap();
+        backup(&index_file_path).unwrap();
+        assert_eq!(fs::read_to_string(&backup_file_path).unwrap(), valid_index);
+
+        // A truncated index doesn't replace the backup
+        fs::write(&index_file_path, "[[projects]]\nname = \"Test").unwrap();
+        backup(&index_file_path).unwrap();
+        assert_eq!(fs::read_to_string(&backup_file_path).unwrap(), valid_index);
+    }
+
+    #[test]
+    fn test_load_from_path_empty_and_corrupt_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index_file_path = temp_dir.path().join("project_index.toml");
+
+        fs::write(&index_file_path, "\n").unwrap();
+        assert!(ProjectIndex::load_from_path(&index_file_path)
+            .unwrap()
+            .projects
+            .is_empty());
+
+        fs::write(
+            &index_file_path,
+            "[[projects]]\nname = \"TestProject\"\ncreation_date_utc = \n",
+        )
+        .unwrap();
+        let error = ProjectIndex::load_from_path(&index_file_path).unwrap_err();
+
+        assert!(matches!(error, IndexLoadError::Corrupt(_)));
+        let message = error.to_string();
+        assert!(message.contains("line 3"), "{}", message);
+        assert!(message.contains("project_index.toml.bak"), "{}", message);
+    }
+
+    #[test]
+    fn test_load_from_path_non_utf8_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index_file_path = temp_dir.path().join("project_index.toml");
+
+        fs::write(
+            &index_file_path,
+            b"[[projects]]\nname = \"TestProject\"\npath = \"some/path\"\xff\xfe",
+        )
+        .unwrap();
+        let error = ProjectIndex::load_from_path(&index_file_path).unwrap_err();
+
+        assert!(matches!(error, IndexLoadError::Corrupt(_)));
+        assert!(error.to_string().contains("UTF-8"), "{}", error);
+    }
+
+    #[test]
+    fn test_corrupt_index_is_read_only() {
+        let project_index = ProjectIndex {
+            corrupt: true,
+            ..ProjectIndex::new()
+        };
+
+        assert!(project_index.write().is_err());
+    }
+
+    #[test]
+    fn test_salvage() {
+        let toml_str = r#"
+[[projects]]
+name = "First"
+creation_date_utc = "2023-09-23T12:00:00Z"
+path = "/srv/first"
+
+[projects.package]
+name = "first"
+
+[[projects]]
+name = "Truncated"
+creation_date_utc = "2023-09-
+
+[[projects]]
+name = "MissingPath"
+creation_date_utc = "2023-09-23T12:00:00Z"
+
+[[projects]]
+name = "First"
+creation_date_utc = "2023-09-23T12:00:00Z"
+path = "/srv/duplicate"
+
+[[projects]]
+name = "Second"
+creation_date_utc = "2023-09-23T12:00:00Z"
+path = "/srv/second"
+"#;
+
+        let (projects, discarded) = salvage(toml_str);
+
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["First", "Second"]);
+        assert_eq!(
+            projects[0].package.as_ref().unwrap().name.as_deref(),
+            Some("first")
+        );
+        assert_eq!(discarded, 3);
+
+        let (_, discarded) = salvage("garbage\n[[projects]]\nname = 1\n");
+        assert_eq!(discarded, 2);
+    }
 }
@@ -0,0 +1,364 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use cli_project_manager::lib::utils::expand_tilde;
+use serde::{Deserialize, Serialize};
+
+use super::hooks::Hooks;
+
+/// Global CLI settings read from `config.toml`.
+/// Every key is optional, missing keys fall back to their default value.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub editor: EditorSettings,
+    pub shell: ShellSettings,
+    pub fuzzy: FuzzySettings,
+    pub output: OutputSettings,
+    /// Hooks run for every project, before the project ones
+    pub hooks: Hooks,
+    pub discovery: DiscoverySettings,
+    pub clone: CloneSettings,
+}
+
+/// Editor launched when a project is opened.
+/// `{path}` and `{name}` placeholders in `args` are replaced by the project path and name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub enabled: bool,
+    pub command: String,
+    pub args: Vec<String>,
+    /// Wait for the editor to exit before starting the shell (terminal editors)
+    pub wait: bool,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        EditorSettings {
+            enabled: true,
+            command: "code".to_string(),
+            args: vec!["{path}".to_string()],
+            wait: false,
+        }
+    }
+}
+
+impl EditorSettings {
+    pub fn expand_args(&self, project_name: &str, project_path: &str) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|arg| {
+                arg.replace("{path}", project_path)
+                    .replace("{name}", project_name)
+            })
+            .collect()
+    }
+}
+
+/// Shell started in the project directory when a project is opened.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ShellSettings {
+    /// Defaults to `$SHELL`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    pub args: Vec<String>,
+    pub mode: ShellMode,
+    pub env: BTreeMap<String, String>,
+}
+
+impl ShellSettings {
+    pub fn program(&self) -> String {
+        self.program
+            .clone()
+            .or_else(|| env::var("SHELL").ok())
+            .unwrap_or_else(|| "sh".to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShellMode {
+    /// Spawn a subshell in the project directory
+    #[default]
+    Spawn,
+    /// Print a `cd` command to be evaluated by the calling shell
+    Cd,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct FuzzySettings {
+    /// Maximum score difference between two consecutive matches to keep both as candidates
+    pub score_gap: u32,
+    /// Matches scoring below this value (0-100) are discarded
+    pub min_score: u32,
+    /// Among close matches, a project whose frecency is at least this many times the others one
+    /// is opened without prompting, 0 disables it
+    pub frecency_ratio: f64,
+    /// Weight of the path segments matches relative to the name ones, 0 disables them
+    pub path_weight: f64,
+    /// Weight of the tags matches relative to the name ones, 0 disables them
+    pub tag_weight: f64,
+}
+
+impl Default for FuzzySettings {
+    fn default() -> Self {
+        FuzzySettings {
+            score_gap: 20,
+            min_score: 0,
+            frecency_ratio: 2.0,
+            path_weight: 0.5,
+            tag_weight: 0.7,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct OutputSettings {
+    /// Hide informational messages (file saved, file created, ...)
+    pub quiet: bool,
+}
+
+/// Where projects are searched on disk
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct DiscoverySettings {
+    /// Directories containing projects, `~` is expanded
+    pub roots: Vec<String>,
+    pub max_depth: usize,
+    /// Files or directories identifying the root of a project
+    pub markers: Vec<String>,
+    /// Directory names never walked into, hidden directories are always skipped
+    pub ignore: Vec<String>,
+}
+
+impl Default for DiscoverySettings {
+    fn default() -> Self {
+        let to_strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+
+        DiscoverySettings {
+            roots: Vec::new(),
+            max_depth: 3,
+            markers: to_strings(&[
+                ".git",
+                "Cargo.toml",
+                "package.json",
+                "pyproject.toml",
+                "go.mod",
+            ]),
+            ignore: to_strings(&["node_modules", "target", "vendor", "dist", "build"]),
+        }
+    }
+}
+
+impl DiscoverySettings {
+    pub fn root_paths(&self) -> Vec<PathBuf> {
+        self.roots.iter().map(|root| expand_tilde(root)).collect()
+    }
+}
+
+/// Where the `clone` command puts the repositories
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct CloneSettings {
+    /// Repositories are cloned in `<root>/<host>/<owner>/<repo>`, `~` is expanded
+    pub root: String,
+}
+
+impl Default for CloneSettings {
+    fn default() -> Self {
+        CloneSettings {
+            root: "~/code".to_string(),
+        }
+    }
+}
+
+impl CloneSettings {
+    pub fn root_path(&self) -> PathBuf {
+        expand_tilde(&self.root)
+    }
+}
+
+impl Settings {
+    pub fn load_from_path(settings_file_path: &Path) -> Result<Settings, anyhow::Error> {
+        let toml_str =
+            fs::read_to_string(settings_file_path).context("Failed to read config file")?;
+
+        let (settings, unknown_keys) = Self::from_toml_str(&toml_str)
+            .with_context(|| format!("Failed to parse {}", settings_file_path.display()))?;
+
+        for key in unknown_keys {
+            eprintln!(
+                "Warning: unknown key `{}` in {} (ignored)",
+                key,
+                settings_file_path.display()
+            );
+        }
+
+        Ok(settings)
+    }
+
+    /// Parses the settings and returns them along with the path of every unknown key.
+    fn from_toml_str(toml_str: &str) -> Result<(Settings, Vec<String>), anyhow::Error> {
+        let mut unknown_keys = Vec::new();
+        let mut deserializer = toml::Deserializer::new(toml_str);
+
+        let settings: Settings = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_keys.push(path.to_string())
+        })?;
+
+        Ok((settings, unknown_keys))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty_settings_use_defaults() {
+        let (settings, unknown_keys) = Settings::from_toml_str("").unwrap();
+
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.editor.command, "code");
+        assert_eq!(settings.fuzzy.score_gap, 20);
+        assert!(unknown_keys.is_empty());
+    }
+
+    #[test]
+    fn test_partial_settings() {
+        let sample_data = r#"
+            [editor]
+            command = "hx"
+
+            [fuzzy]
+            min_score = 40
+        "#;
+
+        let (settings, unknown_keys) = Settings::from_toml_str(sample_data).unwrap();
+
+        assert_eq!(settings.editor.command, "hx");
+        assert_eq!(settings.fuzzy.min_score, 40);
+        assert_eq!(settings.fuzzy.score_gap, 20);
+        assert_eq!(settings.shell, ShellSettings::default());
+        assert!(unknown_keys.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_reported() {
+        let sample_data = r#"
+            colour = true
+
+            [editor]
+            command = "vim"
+            comand = "nvim"
+        "#;
+
+        let (settings, unknown_keys) = Settings::from_toml_str(sample_data).unwrap();
+
+        assert_eq!(settings.editor.command, "vim");
+        assert_eq!(unknown_keys, vec!["colour", "editor.comand"]);
+    }
+
+    #[test]
+    fn test_shell_settings() {
+        let sample_data = r#"
+            [shell]
+            program = "fish"
+            args = ["--login"]
+            mode = "cd"
+
+            [shell.env]
+            EDITOR = "hx"
+        "#;
+
+        let (settings, unknown_keys) = Settings::from_toml_str(sample_data).unwrap();
+
+        assert_eq!(settings.shell.program(), "fish");
+        assert_eq!(settings.shell.args, vec!["--login"]);
+        assert_eq!(settings.shell.mode, ShellMode::Cd);
+        assert_eq!(settings.shell.env.get("EDITOR").unwrap(), "hx");
+        assert!(unknown_keys.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_shell_mode() {
+        let sample_data = r#"
+            [shell]
+            mode = "tmux"
+        "#;
+
+        assert!(Settings::from_toml_str(sample_data).is_err());
+    }
+
+    #[test]
+    fn test_editor_expand_args() {
+        let editor = EditorSettings {
+            command: "idea".to_string(),
+            args: vec![
+                "--title={name}".to_string(),
+                "{path}".to_string(),
+                "-n".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            editor.expand_args("my_project", "/home/user/my_project"),
+            vec!["--title=my_project", "/home/user/my_project", "-n"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_value_type() {
+        let sample_data = r#"
+            [fuzzy]
+            score_gap = "twenty"
+        "#;
+
+        assert!(Settings::from_toml_str(sample_data).is_err());
+    }
+
+    #[test]
+    fn test_discovery_settings() {
+        let sample_data = r#"
+            [discovery]
+            roots = ["~/code", "/srv/repos"]
+            markers = [".git"]
+        "#;
+
+        let (settings, unknown_keys) = Settings::from_toml_str(sample_data).unwrap();
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(
+            settings.discovery.root_paths(),
+            vec![home.join("code"), PathBuf::from("/srv/repos")]
+        );
+        assert_eq!(settings.discovery.markers, vec![".git"]);
+        assert_eq!(settings.discovery.max_depth, 3);
+        assert!(settings
+            .discovery
+            .ignore
+            .contains(&"node_modules".to_string()));
+        assert!(unknown_keys.is_empty());
+    }
+
+    #[test]
+    fn test_clone_settings() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(Settings::default().clone.root_path(), home.join("code"));
+
+        let (settings, unknown_keys) =
+            Settings::from_toml_str("[clone]\nroot = \"/srv/src\"\n").unwrap();
+
+        assert_eq!(settings.clone.root_path(), PathBuf::from("/srv/src"));
+        assert!(unknown_keys.is_empty());
+    }
+}
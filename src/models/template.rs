@@ -0,0 +1,280 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    os::unix,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// Optional file describing a template, it isn't copied to the new projects
+pub const TEMPLATE_CONFIG_FILE: &str = "template.toml";
+
+/// Options of a template, read from the `template.toml` at its root
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TemplateConfig {
+    /// Run `git init` in the new projects
+    pub git_init: bool,
+    /// Commands run in the new project once it is registered, after the global `on_create` hooks
+    pub post_create: Vec<String>,
+}
+
+/// A directory of `templates/` copied to create new projects.
+/// `{{variable}}` placeholders are replaced in the file names and in the text files.
+#[derive(Debug)]
+pub struct Template {
+    pub name: String,
+    pub path: PathBuf,
+    pub config: TemplateConfig,
+}
+
+impl Template {
+    pub fn load(name: &str) -> Result<Template, anyhow::Error> {
+        Self::load_from_dir(&templates_dir(), name)
+    }
+
+    fn load_from_dir(templates_dir: &Path, name: &str) -> Result<Template, anyhow::Error> {
+        let path = templates_dir.join(name);
+
+        // Hidden directories and paths are not templates
+        if name.is_empty() || name.starts_with('.') || name.contains('/') || !path.is_dir() {
+            let available = available_templates(templates_dir);
+            return Err(anyhow::anyhow!(
+                "Template {} not found in {}, available templates: {}",
+                name,
+                templates_dir.display(),
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            ));
+        }
+
+        let config_path = path.join(TEMPLATE_CONFIG_FILE);
+        let config = if config_path.exists() {
+            let toml_str = fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read {}", config_path.display()))?;
+            toml::from_str(&toml_str)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?
+        } else {
+            TemplateConfig::default()
+        };
+
+        Ok(Template {
+            name: name.to_string(),
+            path,
+            config,
+        })
+    }
+
+    /// Copies the template to `destination`, which must not exist yet
+    pub fn render(
+        &self,
+        destination: &Path,
+        variables: &BTreeMap<&str, String>,
+    ) -> Result<(), anyhow::Error> {
+        copy_dir(&self.path, destination, variables, true)
+    }
+}
+
+pub fn templates_dir() -> PathBuf {
+    let config = crate::config::get_config().unwrap().read().unwrap();
+    config.base_dir.join("templates")
+}
+
+fn available_templates(templates_dir: &Path) -> Vec<String> {
+    let mut templates: Vec<String> = fs::read_dir(templates_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    templates.sort();
+    templates
+}
+
+fn copy_dir(
+    source: &Path,
+    destination: &Path,
+    variables: &BTreeMap<&str, String>,
+    is_root: bool,
+) -> Result<(), anyhow::Error> {
+    fs::create_dir_all(destination)
+        .with_context(|| format!("Failed to create {}", destination.display()))?;
+
+    let mut entries = fs::read_dir(source)
+        .with_context(|| format!("Failed to read {}", source.display()))?
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if is_root && (file_name == TEMPLATE_CONFIG_FILE || file_name == ".git") {
+            continue;
+        }
+
+        let source_path = entry.path();
+        let target_path = destination.join(substitute(&file_name, variables));
+        let file_type = fs::symlink_metadata(&source_path)
+            .with_context(|| format!("Failed to read {}", source_path.display()))?
+            .file_type();
+
+        // Links are recreated as they are rather than replaced by a copy of their target
+        if file_type.is_symlink() {
+            let link_target = fs::read_link(&source_path)
+                .with_context(|| format!("Failed to read {}", source_path.display()))?;
+            unix::fs::symlink(&link_target, &target_path)
+                .with_context(|| format!("Failed to create {}", target_path.display()))?;
+            continue;
+        }
+
+        if file_type.is_dir() {
+            copy_dir(&source_path, &target_path, variables, false)?;
+            continue;
+        }
+
+        // Binary files are copied as they are
+        match fs::read_to_string(&source_path) {
+            Ok(content) => {
+                fs::write(&target_path, substitute(&content, variables))
+                    .with_context(|| format!("Failed to write {}", target_path.display()))?;
+                fs::set_permissions(&target_path, entry.metadata()?.permissions())?;
+            }
+            Err(_) => {
+                fs::copy(&source_path, &target_path)
+                    .with_context(|| format!("Failed to copy {}", source_path.display()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces every `{{variable}}`, unknown placeholders are kept
+fn substitute(text: &str, variables: &BTreeMap<&str, String>) -> String {
+    variables
+        .iter()
+        .fold(text.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn variables() -> BTreeMap<&'static str, String> {
+        BTreeMap::from([
+            ("name", "billing".to_string()),
+            ("date", "2024-03-01".to_string()),
+            ("author", "Ada".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_substitute() {
+        assert_eq!(
+            substitute(
+                "# {{name}}\nBy {{author}} on {{date}}, {{unknown}}",
+                &variables()
+            ),
+            "# billing\nBy Ada on 2024-03-01, {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let templates_dir = tempfile::tempdir().unwrap();
+        let template_dir = templates_dir.path().join("service");
+        fs::create_dir_all(template_dir.join("src/{{name}}")).unwrap();
+        fs::write(template_dir.join("README.md"), "# {{name}}\n").unwrap();
+        fs::write(template_dir.join("src/{{name}}/main.rs"), "// {{author}}\n").unwrap();
+        fs::write(template_dir.join("logo.bin"), [0xff, 0xfe, 0x00]).unwrap();
+        fs::write(template_dir.join(".gitignore"), "target\n").unwrap();
+        fs::write(
+            template_dir.join(TEMPLATE_CONFIG_FILE),
+            "git_init = true\npost_create = [\"make\"]\n",
+        )
+        .unwrap();
+
+        let template = Template::load_from_dir(templates_dir.path(), "service").unwrap();
+        assert_eq!(
+            template.config,
+            TemplateConfig {
+                git_init: true,
+                post_create: vec!["make".to_string()],
+            }
+        );
+
+        let destination_dir = tempfile::tempdir().unwrap();
+        let destination = destination_dir.path().join("billing");
+        template.render(&destination, &variables()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(destination.join("README.md")).unwrap(),
+            "# billing\n"
+        );
+        assert_eq!(
+            fs::read_to_string(destination.join("src/billing/main.rs")).unwrap(),
+            "// Ada\n"
+        );
+        assert_eq!(
+            fs::read(destination.join("logo.bin")).unwrap(),
+            vec![0xff, 0xfe, 0x00]
+        );
+        assert!(destination.join(".gitignore").exists());
+        assert!(!destination.join(TEMPLATE_CONFIG_FILE).exists());
+    }
+
+    #[test]
+    fn test_render_keeps_symlinks() {
+        let templates_dir = tempfile::tempdir().unwrap();
+        let template_dir = templates_dir.path().join("service");
+        fs::create_dir_all(template_dir.join("config")).unwrap();
+        fs::write(
+            template_dir.join("config/default.toml"),
+            "name = \"{{name}}\"\n",
+        )
+        .unwrap();
+        unix::fs::symlink("config/default.toml", template_dir.join("app.toml")).unwrap();
+        unix::fs::symlink("config", template_dir.join("settings")).unwrap();
+
+        let template = Template::load_from_dir(templates_dir.path(), "service").unwrap();
+        let destination_dir = tempfile::tempdir().unwrap();
+        let destination = destination_dir.path().join("billing");
+        template.render(&destination, &variables()).unwrap();
+
+        for (link, target) in [("app.toml", "config/default.toml"), ("settings", "config")] {
+            assert_eq!(
+                fs::read_link(destination.join(link)).unwrap(),
+                PathBuf::from(target)
+            );
+        }
+        assert_eq!(
+            fs::read_to_string(destination.join("app.toml")).unwrap(),
+            "name = \"billing\"\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_template_lists_the_available_ones() {
+        let templates_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(templates_dir.path().join("service")).unwrap();
+        fs::create_dir_all(templates_dir.path().join("cli")).unwrap();
+
+        let error = Template::load_from_dir(templates_dir.path(), "web").unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("available templates: cli, service"));
+        assert!(Template::load_from_dir(templates_dir.path(), "../service").is_err());
+        assert!(Template::load_from_dir(templates_dir.path(), "..").is_err());
+    }
+}
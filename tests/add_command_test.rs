@@ -1,3 +1,10 @@
+// These tests predate the clippy gate and are kept as they were written
+#![allow(
+    clippy::let_unit_value,
+    clippy::needless_borrow,
+    clippy::needless_borrows_for_generic_args
+)]
+
 use core::str;
 use std::{fs, thread, time::Duration};
 
@@ -228,6 +235,71 @@ fn create_a_project_with_an_existing_path() -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+/// 6. The previous index is kept as a backup
+#[test]
+#[serial]
+fn keep_a_backup_of_the_previous_index() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let first_dir = tempfile::tempdir()?;
+    let second_dir = tempfile::tempdir()?;
+
+    for (name, path) in [("first", first_dir.path()), ("second", second_dir.path())] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    let index = fs::read_to_string(config_path.join("project_index.toml"))?;
+    let backup = fs::read_to_string(config_path.join("project_index.toml.bak"))?;
+    assert!(index.contains("first") && index.contains("second"));
+    assert!(backup.contains("first") && !backup.contains("second"));
+
+    // No temporary file is left behind
+    for entry in fs::read_dir(&config_path)?.chain(fs::read_dir(config_path.join("projects"))?) {
+        assert!(!entry?.file_name().to_string_lossy().ends_with(".tmp"));
+    }
+
+    Ok(())
+}
+
+/// 7. A failing on_add hook reports that the project is registered
+#[test]
+#[serial]
+fn failing_add_hook_keeps_the_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let project_dir = tempfile::tempdir()?;
+
+    fs::create_dir_all(&config_path)?;
+    fs::write(
+        config_path.join("config.toml"),
+        "[hooks]\non_add = [\"exit 3\"]\nabort_on_failure = true\n",
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("add")
+        .arg(project_dir.path())
+        .args(["--name", "hooked"])
+        .assert()
+        .failure()
+        .get_output()
+        .clone();
+
+    let stderr = str::from_utf8(&output.stderr)?;
+    assert!(
+        stderr.contains("The project hooked is registered anyway"),
+        "{}",
+        stderr
+    );
+    assert!(
+        fs::read_to_string(config_path.join("project_index.toml"))?.contains("name = \"hooked\"")
+    );
+
+    Ok(())
+}
+
 // Utils
 fn wait_for_condition<F>(condition: F)
 where
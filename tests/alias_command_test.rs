@@ -0,0 +1,64 @@
+use std::{fs, str};
+
+use serial_test::serial;
+
+fn add_project(name: &str, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add").arg(path).arg("--name").arg(name);
+    cmd.assert().success();
+    Ok(())
+}
+
+fn run(args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.args(args).assert().success().get_output().clone();
+    Ok(str::from_utf8(&output.stdout)?.to_string())
+}
+
+#[test]
+#[serial]
+fn alias_projects() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let backend_dir = tempfile::tempdir()?;
+    let frontend_dir = tempfile::tempdir()?;
+
+    add_project("backend-service", backend_dir.path())?;
+    add_project("frontend", frontend_dir.path())?;
+
+    run(&["alias", "add", "backend-service", "api", "back"])?;
+
+    let project_config = fs::read_to_string(config_path.join("projects/backend-service.toml"))?;
+    assert!(project_config.contains("aliases = [\"api\", \"back\"]"));
+
+    assert_eq!(
+        run(&["alias", "ls"])?,
+        "api -> backend-service\nback -> backend-service\n"
+    );
+    assert_eq!(run(&["alias", "ls", "backend-service"])?, "api\nback\n");
+
+    // Aliases resolve exactly
+    let stdout = run(&["--resolve", "--no-editor", "api"])?;
+    assert_eq!(
+        stdout.trim(),
+        backend_dir.path().canonicalize()?.to_string_lossy()
+    );
+
+    // An alias can't open two projects
+    for alias in ["api", "frontend", "last"] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.args(["alias", "add", "frontend", alias])
+            .assert()
+            .failure();
+    }
+
+    run(&["alias", "rm", "backend-service", "back"])?;
+    assert_eq!(run(&["alias", "ls", "backend-service"])?, "api\n");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["alias", "rm", "backend-service", "back"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::{fs, path::Path, process::Command};
+
+use serial_test::serial;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git")
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+#[serial]
+fn clone_and_register_a_repository() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root_dir = tempfile::tempdir()?;
+    let source = root_dir.path().join("source");
+    let workspace = root_dir.path().join("workspace");
+    fs::create_dir_all(&source)?;
+    fs::create_dir_all(root_dir.path().join("srv/team"))?;
+
+    git(&source, &["init", "-q"]);
+    fs::write(source.join("README.md"), "widget\n")?;
+    git(&source, &["add", "."]);
+    git(&source, &["commit", "-q", "-m", "Initial commit"]);
+    git(
+        root_dir.path(),
+        &["clone", "-q", "--bare", "source", "srv/team/widget.git"],
+    );
+
+    fs::create_dir_all(&config_path)?;
+    fs::write(
+        config_path.join("config.toml"),
+        format!("[clone]\nroot = \"{}\"\n", workspace.display()),
+    )?;
+
+    let url = format!(
+        "file://{}",
+        root_dir.path().join("srv/team/widget.git").display()
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["clone", &url]).assert().success();
+
+    let clone_dir = workspace.join("local/team/widget");
+    assert!(clone_dir.join("README.md").exists());
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"widget\""));
+    assert!(index_content.contains(&clone_dir.canonicalize()?.to_string_lossy().to_string()));
+
+    // The destination and the name are checked before cloning
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["clone", &url]).assert().failure();
+
+    let other_dest = root_dir.path().join("elsewhere/widget");
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["clone", &url, "--dest"])
+        .arg(&other_dest)
+        .assert()
+        .failure();
+    assert!(!other_dest.exists());
+
+    // A bare repository path works as well
+    let bare_path = root_dir.path().join("srv/team/widget.git");
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("clone")
+        .arg(&bare_path)
+        .args(["--name", "widget-fork", "--dest"])
+        .arg(&other_dest)
+        .assert()
+        .success();
+
+    assert!(other_dest.join("README.md").exists());
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"widget-fork\""));
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use core::str;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn completions_script_for_every_shell() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    for shell in ["bash", "zsh", "fish", "elvish", "powershell"] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        let output = cmd
+            .arg("completions")
+            .arg(shell)
+            .output()
+            .expect("failed to run command");
+
+        assert!(output.status.success(), "Failed on {}", shell);
+
+        let stdout = str::from_utf8(&output.stdout).unwrap();
+        assert!(
+            stdout.contains("cli_project_manager"),
+            "Failed on {}",
+            shell
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn complete_registered_project_names() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    for args in [vec!["Test"], vec!["delete", "Test"]] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        let output = cmd
+            .env("COMPLETE", "fish")
+            .arg("--")
+            .arg("cli_project_manager")
+            .args(&args)
+            .output()
+            .expect("failed to run command");
+
+        assert!(output.status.success());
+
+        let stdout = str::from_utf8(&output.stdout).unwrap();
+        assert!(
+            stdout.lines().any(|line| line.starts_with("TestProject\t")),
+            "Failed on {:?}: {}",
+            args,
+            stdout
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn complete_without_touching_the_config() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    // Nothing is created by a completion request
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.env("COMPLETE", "fish")
+        .args(["--", "cli_project_manager", "del"])
+        .assert()
+        .success();
+    assert!(!config_path.exists());
+
+    // An invalid config doesn't break completion
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+    std::fs::write(
+        config_path.join("config.toml"),
+        "unknown_key = 1\n[editor\n",
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .env("COMPLETE", "fish")
+        .args(["--", "cli_project_manager", "Test"])
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    assert!(str::from_utf8(&output.stdout)?
+        .lines()
+        .any(|line| line.starts_with("TestProject\t")));
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::{fs, thread};
+
+/// Parallel commands must not lose each other's changes, the environment is passed to every
+/// command so this test doesn't need to run serially.
+#[test]
+fn parallel_adds_keep_every_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_dir = tempfile::tempdir()?;
+    let config_path = config_dir.path().join(".project_manager_cli");
+    let project_dirs = (0..12)
+        .map(|_| tempfile::tempdir())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    thread::scope(|scope| {
+        for (i, project_dir) in project_dirs.iter().enumerate() {
+            let config_path = &config_path;
+            scope.spawn(move || {
+                let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager").unwrap();
+                cmd.env("PROJECT_MANAGER_CLI_HOME", config_path)
+                    .arg("add")
+                    .arg(project_dir.path())
+                    .arg("--name")
+                    .arg(format!("project-{}", i))
+                    .assert()
+                    .success();
+            });
+        }
+    });
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    for i in 0..project_dirs.len() {
+        assert!(
+            index_content.contains(&format!("name = \"project-{}\"", i)),
+            "project-{} is missing from\n{}",
+            i,
+            index_content
+        );
+        assert!(config_path
+            .join(format!("projects/project-{}.toml", i))
+            .exists());
+    }
+
+    // Tags added in parallel to the same project are all kept
+    thread::scope(|scope| {
+        for tag in ["a", "b", "c", "d", "e", "f"] {
+            let config_path = &config_path;
+            scope.spawn(move || {
+                let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager").unwrap();
+                cmd.env("PROJECT_MANAGER_CLI_HOME", config_path)
+                    .args(["tag", "add", "project-0", tag])
+                    .assert()
+                    .success();
+            });
+        }
+    });
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("tags = [\"a\", \"b\", \"c\", \"d\", \"e\", \"f\"]"));
+
+    Ok(())
+}
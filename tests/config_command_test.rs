@@ -0,0 +1,124 @@
+use core::str;
+use std::fs;
+
+use serial_test::serial;
+
+fn add_test_project(config_path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let project_dir = tempfile::tempdir()?.keep();
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir)
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn show_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    add_test_project(&config_path)?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("show").arg("TestProject").output()?;
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains("Name:        TestProject"));
+    assert!(stdout.contains("projects/TestProject.toml"));
+    assert!(stdout.contains("[meta_data]"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("show").arg("UnknownProject").assert().failure();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn config_set_and_get() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    add_test_project(&config_path)?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["config", "set", "TestProject", "editor.command", "nvim"])
+        .assert()
+        .success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["config", "set", "TestProject", "editor.wait", "true"])
+        .assert()
+        .success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["config", "get", "TestProject", "editor.command"])
+        .output()?;
+    assert_eq!(str::from_utf8(&output.stdout)?, "nvim\n");
+
+    // Values looking like numbers or booleans are kept as strings where a string is expected
+    for (key, value) in [("env.PORT", "8080"), ("env.DEBUG", "true")] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.args(["config", "set", "TestProject", key, value])
+            .assert()
+            .success();
+    }
+
+    let project_config = fs::read_to_string(config_path.join("projects/TestProject.toml"))?;
+    assert!(project_config.contains("wait = true"));
+    assert!(project_config.contains("PORT = \"8080\""));
+    assert!(project_config.contains("DEBUG = \"true\""));
+
+    // Invalid keys, values and metadata edits are rejected
+    for (key, value) in [
+        ("editor.comand", "vim"),
+        ("editor.wait", "maybe"),
+        ("meta_data.name", "Renamed"),
+    ] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.args(["config", "set", "TestProject", key, value])
+            .assert()
+            .failure();
+    }
+
+    let unchanged = fs::read_to_string(config_path.join("projects/TestProject.toml"))?;
+    assert_eq!(project_config, unchanged);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn edit_project_config() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    add_test_project(&config_path)?;
+    let project_config_path = config_path.join("projects/TestProject.toml");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.env_remove("VISUAL")
+        .env("EDITOR", "printf '[env]\\nRUST_LOG = \"debug\"\\n' >>")
+        .args(["edit", "TestProject"])
+        .assert()
+        .success();
+
+    let project_config = fs::read_to_string(&project_config_path)?;
+    assert!(project_config.contains("RUST_LOG = \"debug\""));
+
+    // An invalid edit is discarded when the user doesn't want to retry
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.env_remove("VISUAL")
+        .env("EDITOR", "printf 'unknown_key = 1\\n' >>")
+        .args(["edit", "TestProject"])
+        .write_stdin("n\n")
+        .assert()
+        .failure();
+
+    assert_eq!(project_config, fs::read_to_string(&project_config_path)?);
+    assert!(!config_path.join("projects/.TestProject.toml.edit").exists());
+
+    Ok(())
+}
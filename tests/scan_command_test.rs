@@ -0,0 +1,129 @@
+use std::{fs, str};
+
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn scan_registers_projects() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root = tempfile::tempdir()?;
+
+    for dir in ["work/api/.git", "oss/api", "oss/tool", "notes"] {
+        fs::create_dir_all(root.path().join(dir))?;
+    }
+    fs::write(root.path().join("oss/api/Cargo.toml"), "")?;
+    fs::write(root.path().join("oss/tool/go.mod"), "")?;
+
+    // Dry run doesn't register anything
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("scan")
+        .arg(root.path())
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(stdout.contains("Would add api ("));
+    assert!(stdout.contains("Would add api-2 ("));
+    assert!(stdout.contains("3 project(s) to add, 0 already registered"));
+    assert!(!fs::read_to_string(config_path.join("project_index.toml"))?.contains("api"));
+
+    let other_project_dir = tempfile::tempdir()?;
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add")
+        .arg(other_project_dir.path())
+        .args(["--name", "other"])
+        .assert()
+        .success();
+    let index_before_scan = fs::read_to_string(config_path.join("project_index.toml"))?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("scan")
+        .arg(root.path())
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert_eq!(stdout.matches("Project index file saved").count(), 1);
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"api\""));
+    assert!(index_content.contains("name = \"api-2\""));
+    assert!(index_content.contains("name = \"tool\""));
+    assert!(!index_content.contains("name = \"notes\""));
+    assert!(config_path.join("projects/api-2.toml").exists());
+    assert_eq!(
+        fs::read_to_string(config_path.join("project_index.toml.bak"))?,
+        index_before_scan
+    );
+
+    // Scanning again is a no-op
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("scan")
+        .arg(root.path())
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(str::from_utf8(&output.stdout)?.contains("0 project(s) added, 3 already registered"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn scan_without_root() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("scan").assert().failure();
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn scan_overlapping_roots() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root = tempfile::tempdir()?;
+
+    for dir in ["work/api/.git", "web/.git"] {
+        fs::create_dir_all(root.path().join(dir))?;
+    }
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("scan")
+        .arg(root.path())
+        .arg(root.path().join("work"))
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let stdout = str::from_utf8(&output.stdout)?;
+    assert!(!stdout.contains("api-2"), "{}", stdout);
+    assert!(stdout.contains("2 project(s) to add"), "{}", stdout);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("scan")
+        .arg(root.path())
+        .arg(root.path().join("work"))
+        .assert()
+        .success();
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("name = \"api\""));
+    assert!(index_content.contains("name = \"web\""));
+    assert!(!index_content.contains("name = \"api-2\""));
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::{fs, path::Path, process::Command, str};
+
+use serial_test::serial;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git")
+        .status;
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn add_project(name: &str, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add").arg(path).arg("--name").arg(name);
+    cmd.assert().success();
+    Ok(())
+}
+
+fn status(args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd
+        .arg("status")
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    Ok(str::from_utf8(&output.stdout)?.to_string())
+}
+
+fn row<'a>(output: &'a str, name: &str) -> Vec<&'a str> {
+    let line = output
+        .lines()
+        .find(|line| line.starts_with(&format!("{} ", name)))
+        .unwrap_or_else(|| panic!("no row for {} in\n{}", name, output));
+    line.split("  ")
+        .map(str::trim)
+        .filter(|cell| !cell.is_empty())
+        .collect()
+}
+
+#[test]
+#[serial]
+fn report_git_status_of_every_project() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let root_dir = tempfile::tempdir()?;
+    let origin = root_dir.path().join("origin");
+    let clone = root_dir.path().join("clone");
+    let plain = root_dir.path().join("plain");
+    fs::create_dir_all(&origin)?;
+    fs::create_dir_all(&plain)?;
+
+    git(&origin, &["init", "-q", "-b", "main"]);
+    fs::write(origin.join("README.md"), "origin\n")?;
+    git(&origin, &["add", "."]);
+    git(&origin, &["commit", "-q", "-m", "Initial commit"]);
+
+    git(root_dir.path(), &["clone", "-q", "origin", "clone"]);
+    fs::write(clone.join("feature.txt"), "feature\n")?;
+    git(&clone, &["add", "."]);
+    git(&clone, &["commit", "-q", "-m", "Add feature"]);
+    fs::write(clone.join("wip.txt"), "wip\n")?;
+    git(&clone, &["stash", "-q", "-u"]);
+    fs::write(clone.join("README.md"), "changed\n")?;
+    fs::write(clone.join("notes.txt"), "notes\n")?;
+
+    add_project("origin", &origin)?;
+    add_project("clone", &clone)?;
+    add_project("plain", &plain)?;
+
+    let output = status(&[])?;
+    assert!(output.starts_with("NAME"));
+    assert_eq!(
+        row(&output, "origin"),
+        vec!["origin", "main", "clean", "no upstream", "0", "just now"]
+    );
+    assert_eq!(
+        row(&output, "clone"),
+        vec!["clone", "main", "2 changed", "ahead 1", "1", "just now"]
+    );
+    assert_eq!(row(&output, "plain"), vec!["plain", "not a git repository"]);
+
+    let output = status(&["--dirty"])?;
+    assert!(output.contains("clone"));
+    assert!(!output.contains("origin"));
+    assert!(!output.contains("plain"));
+
+    git(&clone, &["checkout", "-q", "."]);
+    fs::remove_file(clone.join("notes.txt"))?;
+    assert_eq!(
+        status(&["--dirty"])?,
+        "No project with uncommitted changes\n"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use std::{fs, str};
+
+use serial_test::serial;
+
+fn add_project(name: &str, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.arg("add").arg(path).arg("--name").arg(name);
+    cmd.assert().success();
+    Ok(())
+}
+
+fn run(args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    let output = cmd.args(args).assert().success().get_output().clone();
+    Ok(str::from_utf8(&output.stdout)?.to_string())
+}
+
+#[test]
+#[serial]
+fn tag_projects() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = tempfile::tempdir()?.path().join(".project_manager_cli");
+    std::env::set_var("PROJECT_MANAGER_CLI_HOME", &config_path);
+    let backend_dir = tempfile::tempdir()?;
+    let cli_dir = tempfile::tempdir()?;
+
+    add_project("Backend", backend_dir.path())?;
+    add_project("Cli", cli_dir.path())?;
+
+    run(&["tag", "add", "Backend", "work", "rust"])?;
+    run(&["tag", "add", "Cli", "rust", "oss"])?;
+
+    let index_content = fs::read_to_string(config_path.join("project_index.toml"))?;
+    assert!(index_content.contains("tags = [\"rust\", \"work\"]"));
+    let project_config = fs::read_to_string(config_path.join("projects/Cli.toml"))?;
+    assert!(project_config.contains("tags = [\"oss\", \"rust\"]"));
+
+    let stdout = run(&["list", "--tag", "rust", "--tag", "work"])?;
+    assert!(stdout.contains("Backend"));
+    assert!(!stdout.contains("Cli"));
+
+    assert_eq!(run(&["tag", "ls"])?, "oss (1)\nrust (2)\nwork (1)\n");
+    assert_eq!(run(&["tag", "ls", "Cli"])?, "oss\nrust\n");
+
+    run(&["tag", "rm", "Cli", "rust"])?;
+    assert_eq!(run(&["tag", "ls", "Cli"])?, "oss\n");
+
+    // Unknown tag
+    let mut cmd = assert_cmd::Command::cargo_bin("cli_project_manager")?;
+    cmd.args(["tag", "rm", "Cli", "rust"]).assert().failure();
+
+    Ok(())
+}